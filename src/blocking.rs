@@ -0,0 +1,616 @@
+//! A synchronous client for scripts and build-time code, available with the `blocking` feature.
+//!
+//! It offers the same methods as the async [`Client`](crate::client::Client) and is configured
+//! the same way, with retries, rate limiting and caches, but blocks the current thread instead of
+//! returning futures. Iterators take the place of streams, and the `get_all_*` methods fetch
+//! pages on scoped threads. Like `reqwest::blocking`, it must not be used from within an async runtime.
+//!
+//! ```no_run
+//! # use pokemon_tcg_sdk::{blocking::Client, card::GetCardRequest};
+//! let client = Client::with_api_key("YOUR_API_KEY")?;
+//! let card = client.get_card(GetCardRequest::new("base1-4"))?;
+//! # Ok::<(), pokemon_tcg_sdk::errors::ClientError>(())
+//! ```
+
+use std::{panic, thread, time::Duration};
+
+use bytes::Bytes;
+use reqwest::header::HeaderMap;
+use serde::de::DeserializeOwned;
+
+use crate::{
+    cache::{CacheConfig, DiskCache, Endpoint, ResponseCache, StoredResponse},
+    card::{self, Card, GetCardRequest, SearchCardsRequest},
+    client::{self, DataEnvelope},
+    errors::ClientError,
+    page::Page,
+    query::CardQuery,
+    rate_limit::{RateLimit, RateLimiter},
+    retry::{self, RetryPolicy},
+    schema,
+    set::{GetSetRequest, SearchSetsRequest, Set},
+    transport::{self, select_param, SearchParams},
+    types::{EnergyType, Rarity, Subtype, Supertype},
+};
+
+/// The blocking client for the Pokémon TCG API.
+///
+/// Cloning a client is cheap: clones share the connection pool, the rate limiter and the response cache.
+#[derive(Debug, Clone)]
+pub struct Client {
+    base_url: String,
+    http_client: reqwest::blocking::Client,
+    headers: HeaderMap,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
+    /// How many pages `get_all_*` methods fetch at the same time
+    page_concurrency: usize,
+    cache: Option<ResponseCache>,
+    disk_cache: Option<DiskCache>,
+    strict: bool,
+}
+
+impl Client {
+    /// Constructs a new client
+    ///
+    /// # Errors
+    /// This method fails if the API key is invalid ("\n" etc.)
+    /// or if a TLS backend cannot be initialized, or the resolver
+    /// cannot load the system configuration.
+    pub fn new(api_key: Option<&str>) -> Result<Self, ClientError> {
+        let mut builder = ClientBuilder::new();
+        if let Some(key) = api_key {
+            builder = builder.api_key(key);
+        }
+
+        builder.build()
+    }
+
+    /// Constructs a client with a different base url than the default for the API.
+    ///
+    /// # Errors
+    /// This method fails if the API key is invalid ("\n" etc.)
+    /// or if a TLS backend cannot be initialized, or the resolver
+    /// cannot load the system configuration.
+    pub fn with_base_url(base_url: &str, api_key: Option<&str>) -> Result<Self, ClientError> {
+        let mut builder = ClientBuilder::new().base_url(base_url);
+        if let Some(key) = api_key {
+            builder = builder.api_key(key);
+        }
+
+        builder.build()
+    }
+
+    /// Constructs a client with an API key that will be passed on every request.
+    ///
+    /// # Errors
+    /// This method fails if the API key is invalid ("\n" etc.)
+    /// or if a TLS backend cannot be initialized, or the resolver
+    /// cannot load the system configuration.
+    pub fn with_api_key(api_key: &str) -> Result<Self, ClientError> {
+        Client::new(Some(api_key))
+    }
+
+    /// Returns a builder to configure timeouts, headers, proxies and more.
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
+    /// The response cache of the client, if one was configured with [`ClientBuilder::cache`].
+    pub fn cache(&self) -> Option<&ResponseCache> {
+        self.cache.as_ref()
+    }
+
+    /// The disk cache of the client, if one was configured with [`ClientBuilder::disk_cache`].
+    pub fn disk_cache(&self) -> Option<&DiskCache> {
+        self.disk_cache.as_ref()
+    }
+
+    /// Fetch the details of a single card.
+    ///
+    /// https://docs.pokemontcg.io/api-reference/cards/get-card
+    pub fn get_card(&self, request: GetCardRequest) -> Result<Card, ClientError> {
+        schema::check(self.strict, self.get_card_as(request)?)
+    }
+
+    /// Fetch a single card into any deserializable type, such as a struct holding only
+    /// the fields listed in `request.select` or a `serde_json::Value`.
+    /// A [strict](ClientBuilder::strict) client doesn't check the result for unknown fields.
+    ///
+    /// https://docs.pokemontcg.io/api-reference/cards/get-card
+    pub fn get_card_as<T: DeserializeOwned>(
+        &self,
+        request: GetCardRequest,
+    ) -> Result<T, ClientError> {
+        let card = self.fetch(
+            &format!("/cards/{}", request.id),
+            &[("select", select_param(&request.select))],
+        )?;
+
+        Ok(card.data)
+    }
+
+    /// Search for one or many cards given a search query.
+    ///
+    /// https://docs.pokemontcg.io/api-reference/cards/search-cards
+    pub fn search_cards(&self, request: SearchCardsRequest) -> Result<Page<Card>, ClientError> {
+        schema::check(self.strict, self.search_cards_as(request)?)
+    }
+
+    /// Search for cards, deserializing each result into any type, such as a struct holding only
+    /// the fields listed in `request.select` or a `serde_json::Value`.
+    /// A [strict](ClientBuilder::strict) client doesn't check the result for unknown fields.
+    ///
+    /// https://docs.pokemontcg.io/api-reference/cards/search-cards
+    pub fn search_cards_as<T: DeserializeOwned>(
+        &self,
+        request: SearchCardsRequest,
+    ) -> Result<Page<T>, ClientError> {
+        self.fetch_page("/cards", &request.into())
+    }
+
+    /// Iterate over every card matching a search query, lazily fetching one page at a time.
+    ///
+    /// Paging starts at `request.page` (or the first page) and uses `request.page_size` (or the API default of 250) cards per page.
+    /// Dropping the iterator stops any further requests.
+    ///
+    /// ```no_run
+    /// # use pokemon_tcg_sdk::{blocking::Client, card::SearchCardsRequest};
+    /// let client = Client::default();
+    /// for card in client.iter_cards(SearchCardsRequest::new("set.id:base1")) {
+    ///     println!("{}", card?.name);
+    /// }
+    /// # Ok::<(), pokemon_tcg_sdk::errors::ClientError>(())
+    /// ```
+    pub fn iter_cards(
+        &self,
+        request: SearchCardsRequest,
+    ) -> impl Iterator<Item = Result<Card, ClientError>> + '_ {
+        self.paginate("/cards", request.into())
+            .map(move |card| card.and_then(|card| schema::check(self.strict, card)))
+    }
+
+    /// Get all cards (will take awhile, automatically pages through data)
+    pub fn get_all_cards(&self) -> Result<Vec<Card>, ClientError> {
+        self.get_all_cards_matching(SearchCardsRequest::default())
+    }
+
+    /// Get all cards matching a search query, keeping its order (automatically pages through data).
+    ///
+    /// Once the first page reveals the total count, the remaining pages are fetched on scoped threads,
+    /// up to the limit set by [`ClientBuilder::page_concurrency`] at a time.
+    pub fn get_all_cards_matching(
+        &self,
+        request: SearchCardsRequest,
+    ) -> Result<Vec<Card>, ClientError> {
+        schema::check(self.strict, self.fetch_all("/cards", request.into())?)
+    }
+
+    /// Get every card of a set (automatically pages through data).
+    ///
+    /// Cards are ordered by collector number, with numbers compared naturally (`2` before `10`).
+    /// In sets that also have unprefixed numbers, subsets with a prefix such as `TG` or `SV` are
+    /// placed last.
+    pub fn get_set_cards(&self, set_id: &str) -> Result<Vec<Card>, ClientError> {
+        let mut cards = self.get_all_cards_matching(CardQuery::new().set_id(set_id).into())?;
+        card::sort_by_collector_number(&mut cards);
+        Ok(cards)
+    }
+
+    /// Fetch the details of a single set.
+    ///
+    /// https://docs.pokemontcg.io/api-reference/sets/get-set
+    pub fn get_set(&self, request: GetSetRequest) -> Result<Set, ClientError> {
+        schema::check(self.strict, self.get_set_as(request)?)
+    }
+
+    /// Fetch a single set into any deserializable type, such as a struct holding only
+    /// the fields listed in `request.select` or a `serde_json::Value`.
+    /// A [strict](ClientBuilder::strict) client doesn't check the result for unknown fields.
+    ///
+    /// https://docs.pokemontcg.io/api-reference/sets/get-set
+    pub fn get_set_as<T: DeserializeOwned>(
+        &self,
+        request: GetSetRequest,
+    ) -> Result<T, ClientError> {
+        let set = self.fetch(
+            &format!("/sets/{}", request.id),
+            &[("select", select_param(&request.select))],
+        )?;
+
+        Ok(set.data)
+    }
+
+    /// Search for one or many sets given a search query.
+    ///
+    /// https://docs.pokemontcg.io/api-reference/sets/search-sets
+    pub fn search_sets(&self, request: SearchSetsRequest) -> Result<Page<Set>, ClientError> {
+        schema::check(self.strict, self.search_sets_as(request)?)
+    }
+
+    /// Search for sets, deserializing each result into any type, such as a struct holding only
+    /// the fields listed in `request.select` or a `serde_json::Value`.
+    /// A [strict](ClientBuilder::strict) client doesn't check the result for unknown fields.
+    ///
+    /// https://docs.pokemontcg.io/api-reference/sets/search-sets
+    pub fn search_sets_as<T: DeserializeOwned>(
+        &self,
+        request: SearchSetsRequest,
+    ) -> Result<Page<T>, ClientError> {
+        self.fetch_page("/sets", &request.into())
+    }
+
+    /// Iterate over every set matching a search query, lazily fetching one page at a time.
+    ///
+    /// Paging starts at `request.page` (or the first page) and uses `request.page_size` (or the API default of 250) sets per page.
+    /// Dropping the iterator stops any further requests.
+    pub fn iter_sets(
+        &self,
+        request: SearchSetsRequest,
+    ) -> impl Iterator<Item = Result<Set, ClientError>> + '_ {
+        self.paginate("/sets", request.into())
+            .map(move |set| set.and_then(|set| schema::check(self.strict, set)))
+    }
+
+    /// Get all sets (automatically pages through data)
+    pub fn get_all_sets(&self) -> Result<Vec<Set>, ClientError> {
+        self.get_all_sets_matching(SearchSetsRequest::default())
+    }
+
+    /// Get all sets matching a search query, keeping its order (automatically pages through data).
+    ///
+    /// Once the first page reveals the total count, the remaining pages are fetched on scoped threads,
+    /// up to the limit set by [`ClientBuilder::page_concurrency`] at a time.
+    pub fn get_all_sets_matching(
+        &self,
+        request: SearchSetsRequest,
+    ) -> Result<Vec<Set>, ClientError> {
+        schema::check(self.strict, self.fetch_all("/sets", request.into())?)
+    }
+
+    /// Get all possible types
+    ///
+    /// https://docs.pokemontcg.io/api-reference/types/get-types
+    pub fn get_types(&self) -> Result<Vec<EnergyType>, ClientError> {
+        Ok(self.fetch("/types", &[])?.data)
+    }
+
+    /// Get all possible subtypes
+    ///
+    /// https://docs.pokemontcg.io/api-reference/subtypes/get-subtypes
+    pub fn get_subtypes(&self) -> Result<Vec<Subtype>, ClientError> {
+        Ok(self.fetch("/subtypes", &[])?.data)
+    }
+
+    /// Get all possible supertypes
+    ///
+    /// https://docs.pokemontcg.io/api-reference/supertypes/get-supertypes
+    pub fn get_supertypes(&self) -> Result<Vec<Supertype>, ClientError> {
+        Ok(self.fetch("/supertypes", &[])?.data)
+    }
+
+    /// Get all possible rarities
+    ///
+    /// https://docs.pokemontcg.io/api-reference/rarities/get-rarities
+    pub fn get_rarities(&self) -> Result<Vec<Rarity>, ClientError> {
+        Ok(self.fetch("/rarities", &[])?.data)
+    }
+
+    /// Fetches a path relative to the base url and decodes the data envelope, going through
+    /// the caches and retrying like [`Client::fetch`](crate::client::Client).
+    fn fetch<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, Option<String>)],
+    ) -> Result<DataEnvelope<T>, ClientError> {
+        let url = transport::url(&self.base_url, path, query)?;
+        let key = url.to_string();
+        let mut request = self
+            .http_client
+            .get(url)
+            .headers(self.headers.clone())
+            .build()?;
+        let cache = self.cache.as_ref().zip(Endpoint::from_path(path));
+
+        if let Some((cache, _)) = cache {
+            if let Some(body) = cache.get(&key) {
+                return transport::decode(&body);
+            }
+        }
+
+        let stored = self.disk_cache.as_ref().and_then(|disk| disk.load(&key));
+        if let Some(stored) = &stored {
+            stored.add_validators(request.headers_mut());
+        }
+
+        let mut attempt = 1;
+        let body = loop {
+            let (result, retry_after) = self.fetch_once(&request, stored.as_ref());
+            match transport::retry_delay(&self.retry_policy, &result, attempt, retry_after) {
+                Some(delay) => {
+                    thread::sleep(delay);
+                    attempt += 1;
+                }
+                None => break result?,
+            }
+        };
+
+        let envelope = transport::decode(&body)?;
+        if let Some((cache, endpoint)) = cache {
+            cache.insert(&key, endpoint, body);
+        }
+
+        Ok(envelope)
+    }
+
+    /// Makes a single attempt at a request, returning the body of a successful response
+    /// and the `Retry-After` delay the API asked for, if any.
+    fn fetch_once(
+        &self,
+        request: &reqwest::blocking::Request,
+        stored: Option<&StoredResponse>,
+    ) -> (Result<Bytes, ClientError>, Option<Duration>) {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            thread::sleep(rate_limiter.reserve());
+        }
+
+        // GET requests have no body, so they can always be cloned
+        let request = request.try_clone().expect("GET requests can be cloned");
+        let url = request.url().clone();
+        let response = match self.http_client.execute(request) {
+            Ok(response) => response,
+            Err(e) => return (Err(e.into()), None),
+        };
+
+        let retry_after = retry::retry_after(response.headers());
+        let status = response.status();
+        let headers = response.headers().clone();
+        let result = match response.bytes() {
+            Ok(body) => transport::read_response(stored, status, body)
+                .map(|body| body.save(self.disk_cache.as_ref(), stored, url.as_str(), &headers)),
+            Err(e) => Err(e.into()),
+        };
+
+        (result, retry_after)
+    }
+
+    fn fetch_page<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        params: &SearchParams,
+    ) -> Result<Page<T>, ClientError> {
+        let resp = self.fetch::<Vec<T>>(path, &params.to_query())?;
+
+        Ok(params.page_of(resp))
+    }
+
+    /// Fetches every page of a search endpoint. The first page tells how many pages there are,
+    /// the others are then fetched in batches of `page_concurrency` scoped threads, keeping their order.
+    fn fetch_all<T: DeserializeOwned + Send>(
+        &self,
+        path: &str,
+        params: SearchParams,
+    ) -> Result<Vec<T>, ClientError> {
+        let params = params.first_page();
+        let first = self.fetch_page::<T>(path, &params)?;
+        let last_page = u16::try_from(first.total_pages()).unwrap_or(u16::MAX);
+        let mut items = first.data;
+        let remaining: Vec<u16> = (first.page.saturating_add(1)..=last_page).collect();
+
+        for batch in remaining.chunks(self.page_concurrency) {
+            let pages = thread::scope(|scope| {
+                let handles: Vec<_> = batch
+                    .iter()
+                    .map(|page| {
+                        let params = params.with_page(*page);
+                        scope.spawn(move || self.fetch_page::<T>(path, &params))
+                    })
+                    .collect();
+
+                handles
+                    .into_iter()
+                    .map(|handle| handle.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+                    .collect::<Vec<_>>()
+            });
+
+            for page in pages {
+                items.extend(page?.data);
+            }
+        }
+
+        Ok(items)
+    }
+
+    /// Lazily pages through a search endpoint. The next page is only requested
+    /// once every item of the previous page has been consumed.
+    fn paginate<T: DeserializeOwned>(
+        &self,
+        path: &'static str,
+        params: SearchParams,
+    ) -> impl Iterator<Item = Result<T, ClientError>> + '_ {
+        let mut next = Some(params.first_page());
+
+        std::iter::from_fn(move || {
+            let params = next.take()?;
+            let page = match self.fetch_page::<T>(path, &params) {
+                Ok(page) => page,
+                Err(e) => return Some(vec![Err(e)]),
+            };
+
+            next = params.next_page(&page);
+            Some(page.data.into_iter().map(Ok).collect())
+        })
+        .flatten()
+    }
+}
+
+/// Configures and constructs a blocking [`Client`].
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use pokemon_tcg_sdk::blocking::Client;
+/// let client = Client::builder()
+///     .api_key("YOUR_API_KEY")
+///     .timeout(Duration::from_secs(30))
+///     .build()?;
+/// # Ok::<(), pokemon_tcg_sdk::errors::ClientError>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct ClientBuilder {
+    /// The options shared with the async client
+    options: client::ClientBuilder,
+    http_client: Option<reqwest::blocking::Client>,
+}
+
+impl ClientBuilder {
+    pub fn new() -> Self {
+        ClientBuilder::default()
+    }
+
+    /// Sets the base url of the API. Defaults to `https://api.pokemontcg.io/v2`.
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.options = self.options.base_url(base_url);
+        self
+    }
+
+    /// Sets an API key that will be passed on every request.
+    pub fn api_key(mut self, api_key: &str) -> Self {
+        self.options = self.options.api_key(api_key);
+        self
+    }
+
+    /// Sets a timeout for only the connect phase of a request.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.options = self.options.connect_timeout(timeout);
+        self
+    }
+
+    /// Sets a timeout for the whole request, from connecting until the response body has been read.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.options = self.options.timeout(timeout);
+        self
+    }
+
+    /// Sets the `User-Agent` header sent with every request.
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.options = self.options.user_agent(user_agent);
+        self
+    }
+
+    /// Routes all requests through the given proxy.
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.options = self.options.proxy(proxy);
+        self
+    }
+
+    /// Sets headers that will be sent with every request.
+    /// The API key and user agent take precedence over headers of the same name.
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.options = self.options.default_headers(headers);
+        self
+    }
+
+    /// Uses an already configured blocking reqwest client for all requests.
+    ///
+    /// Transport options of this builder (timeouts and proxy) are ignored in that case and must be
+    /// configured on the supplied client instead. Headers are still applied to every request.
+    pub fn http_client(mut self, http_client: reqwest::blocking::Client) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    /// Retries failed requests according to the given policy. By default, requests are not retried.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.options = self.options.retry_policy(retry_policy);
+        self
+    }
+
+    /// Throttles outgoing requests, including retries, to the given rate.
+    /// The limit is shared by every endpoint and by all clones of the built client.
+    pub fn rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.options = self.options.rate_limit(rate_limit);
+        self
+    }
+
+    /// Sets how many pages `get_all_*` methods fetch at the same time. Defaults to 4, zero is treated as one.
+    pub fn page_concurrency(mut self, page_concurrency: usize) -> Self {
+        self.options = self.options.page_concurrency(page_concurrency);
+        self
+    }
+
+    /// Caches successful responses in memory, keyed by their URL and query parameters.
+    pub fn cache(mut self, config: CacheConfig) -> Self {
+        self.options = self.options.cache(config);
+        self
+    }
+
+    /// Stores responses on disk and revalidates them with conditional requests.
+    pub fn disk_cache(mut self, disk_cache: DiskCache) -> Self {
+        self.options = self.options.disk_cache(disk_cache);
+        self
+    }
+
+    /// Fails requests whose cards or sets have fields the models don't know about.
+    ///
+    /// The check applies to every response, including those served from the caches. The `_as`
+    /// methods, which decode into types of the caller's choosing, are exempt.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.options = self.options.strict(strict);
+        self
+    }
+
+    /// Constructs the client.
+    ///
+    /// # Errors
+    /// This method fails if the API key or user agent are invalid header values ("\n" etc.)
+    /// or if a TLS backend cannot be initialized, or the resolver
+    /// cannot load the system configuration.
+    pub fn build(self) -> Result<Client, ClientError> {
+        let options = self.options;
+        let headers = options.headers()?;
+        let base_url = options.base_url_or_default();
+
+        let http_client = match self.http_client {
+            Some(http_client) => http_client,
+            None => {
+                let mut builder = reqwest::blocking::Client::builder();
+                if let Some(timeout) = options.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                if let Some(timeout) = options.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(proxy) = options.proxy {
+                    builder = builder.proxy(proxy);
+                }
+                builder.build()?
+            }
+        };
+
+        Ok(Client {
+            base_url,
+            http_client,
+            headers,
+            retry_policy: options.retry_policy.unwrap_or_else(RetryPolicy::none),
+            rate_limiter: options.rate_limit.map(RateLimiter::new),
+            page_concurrency: options
+                .page_concurrency
+                .unwrap_or(client::DEFAULT_PAGE_CONCURRENCY),
+            cache: options.cache.map(ResponseCache::new),
+            disk_cache: options.disk_cache,
+            strict: options.strict,
+        })
+    }
+}
+
+impl Default for Client {
+    /// Constructs a basic client with no API Key using the default URL.
+    ///
+    /// # Panics
+    /// This method will panic if the construction of the reqwest http client fails,
+    /// if a TLS backend cannot be initialized, or the resolver
+    /// cannot load the system configuration.
+    fn default() -> Self {
+        Self::new(None).unwrap()
+    }
+}
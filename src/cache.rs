@@ -0,0 +1,278 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use lru::LruCache;
+
+use crate::time::Instant;
+
+mod disk;
+
+pub(crate) use self::disk::{unblock, StoredResponse};
+pub use self::disk::{DiskCache, DiskCacheEntry};
+
+/// The API endpoints whose responses can be cached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    /// A single card, `/cards/{id}`
+    Card,
+    /// Card searches, `/cards`
+    Cards,
+    /// A single set, `/sets/{id}`
+    Set,
+    /// Set searches, `/sets`
+    Sets,
+    /// The energy types, `/types`
+    Types,
+    /// The subtypes, `/subtypes`
+    Subtypes,
+    /// The supertypes, `/supertypes`
+    Supertypes,
+    /// The rarities, `/rarities`
+    Rarities,
+}
+
+impl Endpoint {
+    /// Finds the endpoint of a path relative to the base url, such as `/cards/base1-4`.
+    pub(crate) fn from_path(path: &str) -> Option<Endpoint> {
+        let mut segments = path.trim_start_matches('/').splitn(2, '/');
+        let endpoint = match (segments.next()?, segments.next()) {
+            ("cards", None) => Endpoint::Cards,
+            ("cards", Some(_)) => Endpoint::Card,
+            ("sets", None) => Endpoint::Sets,
+            ("sets", Some(_)) => Endpoint::Set,
+            ("types", None) => Endpoint::Types,
+            ("subtypes", None) => Endpoint::Subtypes,
+            ("supertypes", None) => Endpoint::Supertypes,
+            ("rarities", None) => Endpoint::Rarities,
+            _ => return None,
+        };
+
+        Some(endpoint)
+    }
+}
+
+/// Configures the in-memory response cache of a [`Client`](crate::client::Client).
+///
+/// ```
+/// # use std::time::Duration;
+/// # use pokemon_tcg_sdk::cache::{CacheConfig, Endpoint};
+/// // Keep reference data for a day, don't cache searches, and hold at most 16 MiB
+/// let config = CacheConfig::new(Duration::from_secs(600))
+///     .ttl(Endpoint::Types, Duration::from_secs(86_400))
+///     .ttl(Endpoint::Cards, Duration::ZERO)
+///     .max_bytes(16 * 1024 * 1024);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CacheConfig {
+    default_ttl: Duration,
+    ttls: HashMap<Endpoint, Duration>,
+    max_entries: Option<usize>,
+    max_bytes: Option<usize>,
+}
+
+impl CacheConfig {
+    /// Caches the responses of every endpoint for `default_ttl`.
+    pub fn new(default_ttl: Duration) -> Self {
+        CacheConfig {
+            default_ttl,
+            ttls: HashMap::new(),
+            max_entries: None,
+            max_bytes: None,
+        }
+    }
+
+    /// Overrides how long responses of an endpoint are kept. A zero TTL disables caching for it.
+    pub fn ttl(mut self, endpoint: Endpoint, ttl: Duration) -> Self {
+        self.ttls.insert(endpoint, ttl);
+        self
+    }
+
+    /// Limits the number of cached responses. The least recently used ones are evicted first.
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = Some(max_entries);
+        self
+    }
+
+    /// Limits the total size of cached response bodies. The least recently used ones are evicted first.
+    pub fn max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    fn ttl_for(&self, endpoint: Endpoint) -> Duration {
+        self.ttls
+            .get(&endpoint)
+            .copied()
+            .unwrap_or(self.default_ttl)
+    }
+}
+
+/// An in-memory cache of API responses, keyed by their full URL including query parameters.
+///
+/// The cache is shared by a client and all of its clones.
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    config: Arc<CacheConfig>,
+    entries: Arc<Mutex<Entries>>,
+}
+
+#[derive(Debug)]
+struct Entries {
+    /// The entries from the most to the least recently used
+    lru: LruCache<String, Entry>,
+    /// The total size of the entries, kept up to date on every change
+    bytes: usize,
+}
+
+#[derive(Debug)]
+struct Entry {
+    endpoint: Endpoint,
+    body: Bytes,
+    expires_at: Instant,
+}
+
+impl Entry {
+    fn size(&self, key: &str) -> usize {
+        key.len() + self.body.len()
+    }
+}
+
+impl ResponseCache {
+    pub(crate) fn new(config: CacheConfig) -> Self {
+        ResponseCache {
+            config: Arc::new(config),
+            entries: Arc::new(Mutex::new(Entries {
+                lru: LruCache::unbounded(),
+                bytes: 0,
+            })),
+        }
+    }
+
+    /// Returns the cached body for a URL, unless it has expired.
+    pub(crate) fn get(&self, url: &str) -> Option<Bytes> {
+        let mut entries = self.lock();
+        let now = Instant::now();
+        match entries.lru.get(url)? {
+            entry if entry.expires_at > now => Some(entry.body.clone()),
+            _ => {
+                entries.remove(url);
+                None
+            }
+        }
+    }
+
+    /// Stores a response body, evicting the least recently used entries if the cache is full.
+    pub(crate) fn insert(&self, url: &str, endpoint: Endpoint, body: Bytes) {
+        let ttl = self.config.ttl_for(endpoint);
+        if ttl.is_zero() {
+            return;
+        }
+
+        let mut entries = self.lock();
+        entries.remove(url);
+
+        let now = Instant::now();
+        let entry = Entry {
+            endpoint,
+            body,
+            expires_at: now + ttl,
+        };
+        if self
+            .config
+            .max_bytes
+            .is_some_and(|max| entry.size(url) > max)
+        {
+            return;
+        }
+        entries.bytes += entry.size(url);
+        entries.lru.put(String::from(url), entry);
+
+        // Expired entries at the end of the list would be evicted first anyway
+        while entries
+            .lru
+            .peek_lru()
+            .is_some_and(|(_, entry)| entry.expires_at <= now)
+        {
+            entries.pop_lru();
+        }
+
+        while self
+            .config
+            .max_entries
+            .is_some_and(|max| entries.lru.len() > max)
+            || self.config.max_bytes.is_some_and(|max| entries.bytes > max)
+        {
+            if !entries.pop_lru() {
+                break;
+            }
+        }
+    }
+
+    /// Removes the cached response for a full URL, such as `https://api.pokemontcg.io/v2/types`.
+    pub fn invalidate(&self, url: &str) {
+        self.lock().remove(url);
+    }
+
+    /// Removes every cached response of an endpoint.
+    pub fn invalidate_endpoint(&self, endpoint: Endpoint) {
+        let mut entries = self.lock();
+        let keys: Vec<String> = entries
+            .lru
+            .iter()
+            .filter(|(_, entry)| entry.endpoint == endpoint)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in keys {
+            entries.remove(&key);
+        }
+    }
+
+    /// Removes every cached response.
+    pub fn clear(&self) {
+        let mut entries = self.lock();
+        entries.lru.clear();
+        entries.bytes = 0;
+    }
+
+    /// The number of cached responses, including expired ones that haven't been evicted yet.
+    pub fn len(&self) -> usize {
+        self.lock().lru.len()
+    }
+
+    /// Whether no responses are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The total size of the cached responses in bytes.
+    pub fn size_bytes(&self) -> usize {
+        self.lock().bytes
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Entries> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Entries {
+    fn remove(&mut self, key: &str) {
+        if let Some(entry) = self.lru.pop(key) {
+            self.bytes -= entry.size(key);
+        }
+    }
+
+    /// Evicts the least recently used entry, returning whether there was one
+    fn pop_lru(&mut self) -> bool {
+        match self.lru.pop_lru() {
+            Some((key, entry)) => {
+                self.bytes -= entry.size(&key);
+                true
+            }
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,272 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use reqwest::header::{
+    HeaderMap, HeaderValue, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::time::{SystemTime, UNIX_EPOCH};
+
+/// A persistent cache of API responses stored as files under a directory.
+///
+/// Responses are stored with their `ETag` and `Last-Modified` validators. Later requests for the
+/// same URL are made conditional, and a `304 Not Modified` answer is served from disk, so the
+/// cache survives restarts without ever returning data the API considers stale.
+///
+/// The async client reads and writes the files on tokio's blocking thread pool. There is no disk
+/// cache in the browser, where `ClientBuilder::disk_cache` isn't available.
+///
+/// ```no_run
+/// # use pokemon_tcg_sdk::{cache::DiskCache, client::Client};
+/// let client = Client::builder()
+///     .disk_cache(DiskCache::new("/tmp/pokemon-tcg-cache"))
+///     .build()?;
+/// # Ok::<(), pokemon_tcg_sdk::errors::ClientError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct DiskCache {
+    dir: PathBuf,
+}
+
+/// The metadata of a response stored in a [`DiskCache`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiskCacheEntry {
+    /// The full URL of the request, including query parameters.
+    pub url: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    /// When the response was stored or last revalidated.
+    pub stored_at: SystemTime,
+    /// The size of the entry on disk in bytes.
+    pub size: u64,
+    path: PathBuf,
+}
+
+/// The format of a cache file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct StoredResponse {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    /// Seconds since the unix epoch
+    stored_at: u64,
+    body: String,
+}
+
+impl DiskCache {
+    /// Stores responses under `dir`, which is created on the first write.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        DiskCache { dir: dir.into() }
+    }
+
+    /// The directory the responses are stored in.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Lists the stored responses. Files that aren't valid cache entries are skipped.
+    pub fn entries(&self) -> io::Result<Vec<DiskCacheEntry>> {
+        let read_dir = match fs::read_dir(&self.dir) {
+            Ok(read_dir) => read_dir,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e),
+        };
+
+        let mut entries = vec![];
+        for file in read_dir {
+            let path = file?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                if let Some(entry) = read_entry(&path) {
+                    entries.push(entry);
+                }
+            }
+        }
+
+        entries.sort_by_key(|entry| entry.stored_at);
+        Ok(entries)
+    }
+
+    /// Returns the metadata of the stored response for a full URL.
+    pub fn entry(&self, url: &str) -> Option<DiskCacheEntry> {
+        read_entry(&self.path_for(url)).filter(|entry| entry.url == url)
+    }
+
+    /// Removes the stored response for a full URL, returning whether there was one.
+    pub fn remove(&self, url: &str) -> io::Result<bool> {
+        if self.entry(url).is_none() {
+            return Ok(false);
+        }
+
+        fs::remove_file(self.path_for(url))?;
+        Ok(true)
+    }
+
+    /// Removes the responses stored or revalidated longer than `max_age` ago,
+    /// returning how many were removed.
+    pub fn prune_older_than(&self, max_age: Duration) -> io::Result<usize> {
+        let now = SystemTime::now();
+        let mut removed = 0;
+        for entry in self.entries()? {
+            let age = now.duration_since(entry.stored_at).unwrap_or_default();
+            if age > max_age {
+                fs::remove_file(&entry.path)?;
+                removed += 1;
+            }
+        }
+
+        Ok(removed)
+    }
+
+    /// Removes the oldest responses until the cache takes at most `max_bytes` on disk,
+    /// returning how many were removed.
+    pub fn prune_to_size(&self, max_bytes: u64) -> io::Result<usize> {
+        let entries = self.entries()?;
+        let mut size: u64 = entries.iter().map(|entry| entry.size).sum();
+        let mut removed = 0;
+        for entry in entries {
+            if size <= max_bytes {
+                break;
+            }
+            fs::remove_file(&entry.path)?;
+            size -= entry.size;
+            removed += 1;
+        }
+
+        Ok(removed)
+    }
+
+    /// Removes every stored response.
+    pub fn clear(&self) -> io::Result<()> {
+        for entry in self.entries()? {
+            fs::remove_file(&entry.path)?;
+        }
+
+        Ok(())
+    }
+
+    /// Loads the stored response for a full URL
+    pub(crate) fn load(&self, url: &str) -> Option<StoredResponse> {
+        let stored: StoredResponse =
+            serde_json::from_slice(&fs::read(self.path_for(url)).ok()?).ok()?;
+        (stored.url == url).then_some(stored)
+    }
+
+    /// Stores a response if it has validators to revalidate it with. Failing to write is not
+    /// an error for the request, the response is simply fetched again next time.
+    pub(crate) fn store(&self, url: &str, headers: &HeaderMap, body: &Bytes) {
+        let header = |name| {
+            headers
+                .get(name)
+                .and_then(|value: &HeaderValue| value.to_str().ok())
+                .map(String::from)
+        };
+        let (etag, last_modified) = (header(ETAG), header(LAST_MODIFIED));
+        if etag.is_none() && last_modified.is_none() {
+            return;
+        }
+        let Ok(body) = String::from_utf8(body.to_vec()) else {
+            return;
+        };
+
+        let stored = StoredResponse {
+            url: String::from(url),
+            etag,
+            last_modified,
+            stored_at: unix_seconds(SystemTime::now()),
+            body,
+        };
+        let _ = self.write(&stored);
+    }
+
+    /// Marks a stored response as fresh after the API confirmed it hasn't changed
+    pub(crate) fn touch(&self, stored: &StoredResponse) {
+        let stored = StoredResponse {
+            stored_at: unix_seconds(SystemTime::now()),
+            ..stored.clone()
+        };
+        let _ = self.write(&stored);
+    }
+
+    fn write(&self, stored: &StoredResponse) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+
+        // Write to a temporary file first so that readers never see a partial entry
+        let path = self.path_for(&stored.url);
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_vec(stored)?)?;
+        fs::rename(tmp, path)
+    }
+
+    fn path_for(&self, url: &str) -> PathBuf {
+        self.dir
+            .join(format!("{:016x}.json", fnv1a(url.as_bytes())))
+    }
+}
+
+impl StoredResponse {
+    /// Adds the `If-None-Match` and `If-Modified-Since` headers that make a request conditional
+    pub(crate) fn add_validators(&self, headers: &mut HeaderMap) {
+        let validators = [
+            (IF_NONE_MATCH, &self.etag),
+            (IF_MODIFIED_SINCE, &self.last_modified),
+        ];
+        for (name, value) in validators {
+            if let Some(value) = value.as_deref().and_then(|v| HeaderValue::from_str(v).ok()) {
+                headers.insert(name, value);
+            }
+        }
+    }
+
+    pub(crate) fn body(&self) -> Bytes {
+        Bytes::from(self.body.clone())
+    }
+}
+
+fn read_entry(path: &Path) -> Option<DiskCacheEntry> {
+    let contents = fs::read(path).ok()?;
+    let stored: StoredResponse = serde_json::from_slice(&contents).ok()?;
+
+    Some(DiskCacheEntry {
+        url: stored.url,
+        etag: stored.etag,
+        last_modified: stored.last_modified,
+        stored_at: UNIX_EPOCH + Duration::from_secs(stored.stored_at),
+        size: contents.len() as u64,
+        path: path.to_path_buf(),
+    })
+}
+
+/// Runs file system work of the disk cache on the blocking thread pool, so that the async
+/// client doesn't stall the executor. The browser has no file system nor threads, and no disk
+/// cache, so the work runs inline there.
+pub(crate) async fn unblock<T, F>(work: F) -> T
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    #[cfg(not(target_arch = "wasm32"))]
+    return tokio::task::spawn_blocking(work)
+        .await
+        .unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic()));
+
+    #[cfg(target_arch = "wasm32")]
+    work()
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// The 64-bit FNV-1a hash, stable across builds unlike the hasher of the standard library
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
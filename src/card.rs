@@ -2,7 +2,9 @@ pub mod ability;
 pub mod ancient_trait;
 pub mod attack;
 pub mod cardmarket;
+pub mod id;
 pub mod image;
+#[deprecated(note = "use `pokemon_tcg_sdk::legality` instead")]
 pub mod legality;
 pub mod resistance;
 pub mod tcgplayer;
@@ -11,15 +13,29 @@ pub mod weakness;
 use std::str::FromStr;
 
 use crate::{
-    client::{ApiResult, Client},
-    errors::ClientError,
+    client::Client,
+    errors::{ClientError, InvalidCardId},
+    legality::Legalities,
+    page::Page,
+    query::{CardQuery, CardSort, OrderBy},
+    schema,
     set::Set,
+    transport::{select_param, SearchParams},
+    types::{EnergyType, Rarity, Subtype, Supertype},
 };
-use serde::{Deserialize, Serialize};
+use futures::{Stream, StreamExt};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 use self::{
-    ability::Ability, ancient_trait::AncientTrait, attack::Attack, cardmarket::CardMarket,
-    image::Image, legality::Legality, resistance::Resistance, tcgplayer::TcgPlayer,
+    ability::Ability,
+    ancient_trait::AncientTrait,
+    attack::Attack,
+    cardmarket::CardMarket,
+    id::{CardId, CollectorNumber},
+    image::Image,
+    resistance::Resistance,
+    tcgplayer::TcgPlayer,
     weakness::Weakness,
 };
 
@@ -33,15 +49,15 @@ pub struct Card {
     /// The name of the card.
     pub name: String,
     /// The supertype of the card, such as Pokémon, Energy, or Trainer.
-    pub supertype: String,
+    pub supertype: Supertype,
     /// A list of subtypes, such as Basic, EX, Mega, Rapid Strike, etc.
-    pub subtypes: Option<Vec<String>>,
+    pub subtypes: Option<Vec<Subtype>>,
     /// The level of the card. This only pertains to cards from older sets and those of supertype Pokémon.
     pub level: Option<String>,
-    /// The hit points of the card.
+    /// The hit points of the card. See [`Card::hp()`] for the parsed value.
     pub hp: Option<String>,
     /// The energy types for a card, such as Fire, Water, Grass, etc.
-    pub types: Option<Vec<String>>,
+    pub types: Option<Vec<EnergyType>>,
     /// Which Pokémon this card evolves from.
     #[serde(alias = "evolvesFrom")]
     pub evolves_from: Option<String>,
@@ -63,7 +79,7 @@ pub struct Card {
     pub resistances: Option<Vec<Resistance>>,
     /// A list of costs it takes to retreat and return the card to your bench. Each cost is an energy type, such as Water or Fire.
     #[serde(alias = "retreatCost")]
-    pub retreat_cost: Option<Vec<String>>,
+    pub retreat_cost: Option<Vec<EnergyType>>,
     /// The converted retreat cost for a card is the count of energy types found within the retreatCost field. For example, ["Fire", "Water"] has a converted retreat cost of 2.
     #[serde(alias = "convertedRetreatCost")]
     pub converted_retreat_cost: Option<usize>,
@@ -74,7 +90,7 @@ pub struct Card {
     /// The artist of the card.
     pub artist: Option<String>,
     /// The rarity of the card, such as "Common" or "Rare Rainbow".
-    pub rarity: Option<String>,
+    pub rarity: Option<Rarity>,
     /// The flavor text of the card. This is the text that can be found on some Pokémon cards that is usually italicized near the bottom of the card.
     #[serde(alias = "flavorText")]
     pub flavor_text: Option<String>,
@@ -82,7 +98,7 @@ pub struct Card {
     #[serde(alias = "nationalPokedexNumbers")]
     pub national_pokedex_numbers: Option<Vec<usize>>,
     /// The legalities for a given card. A legality will not be present in the hash if it is not legal. If it is legal or banned, it will be present.
-    pub legalities: Option<Legality>,
+    pub legalities: Option<Legalities>,
     /// A letter symbol found on each card that identifies whether it is legal to use in tournament play. Regulation marks were introduced on cards in the Sword & Shield Series.
     #[serde(alias = "regulationMark")]
     pub regulation_mark: Option<String>,
@@ -92,18 +108,46 @@ pub struct Card {
     pub tcgplayer: Option<TcgPlayer>,
     /// The cardmarket information for a given card. ALL PRICES ARE IN EUROS.
     pub cardmarket: Option<CardMarket>,
+    /// Fields returned by the API that this version of the crate doesn't model.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
+impl Card {
+    /// Parses the hit points of the card. `None` for cards without hit points, such as Trainers.
+    pub fn hp(&self) -> Option<u32> {
+        self.hp.as_deref()?.trim().parse().ok()
+    }
+
+    /// Splits the id of the card into its set id and collector number.
+    pub fn card_id(&self) -> Result<CardId, InvalidCardId> {
+        self.id.parse()
+    }
+
+    /// The collector number of the card, such as `25` or `TG12`.
+    pub fn collector_number(&self) -> Option<CollectorNumber> {
+        self.number.as_deref().map(CollectorNumber::from)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct GetCardRequest {
     pub id: String,
+    /// The fields to return, such as `id`, `name` or `images`. All fields are returned if empty.
+    /// Partial responses can be fetched with [`Client::get_card_as`].
+    pub select: Option<Vec<String>>,
 }
 
 impl GetCardRequest {
     pub fn new(id: &str) -> Self {
-        GetCardRequest { id: id.into() }
+        GetCardRequest {
+            id: id.into(),
+            select: None,
+        }
     }
 }
 
+#[derive(Debug, Clone, Default)]
 pub struct SearchCardsRequest {
     ///The search query.
     pub query: Option<String>,
@@ -112,7 +156,10 @@ pub struct SearchCardsRequest {
     /// The maximum amount of cards to return. Max of 250.
     pub page_size: Option<u8>,
     /// The field(s) to order the results by.
-    pub order_by: Option<String>,
+    pub order_by: Option<OrderBy<CardSort>>,
+    /// The fields to return, such as `id`, `name` or `images`. All fields are returned if empty.
+    /// Partial responses can be fetched with [`Client::search_cards_as`].
+    pub select: Option<Vec<String>>,
 }
 
 impl SearchCardsRequest {
@@ -122,6 +169,19 @@ impl SearchCardsRequest {
             page: None,
             page_size: None,
             order_by: None,
+            select: None,
+        }
+    }
+}
+
+impl From<SearchCardsRequest> for SearchParams {
+    fn from(request: SearchCardsRequest) -> Self {
+        SearchParams {
+            select: select_param(&request.select),
+            query: request.query,
+            page: request.page,
+            page_size: request.page_size,
+            order_by: request.order_by.map(|o| o.to_string()),
         }
     }
 }
@@ -131,15 +191,26 @@ impl Client {
     ///
     /// https://docs.pokemontcg.io/api-reference/cards/get-card
     pub async fn get_card(&self, request: GetCardRequest) -> Result<Card, ClientError> {
+        schema::check(self.strict, self.get_card_as(request).await?)
+    }
+
+    /// Fetch a single card into any deserializable type, such as a struct holding only
+    /// the fields listed in `request.select` or a `serde_json::Value`.
+    /// A [strict](crate::client::ClientBuilder::strict) client doesn't check the result for unknown fields.
+    ///
+    /// https://docs.pokemontcg.io/api-reference/cards/get-card
+    pub async fn get_card_as<T: DeserializeOwned>(
+        &self,
+        request: GetCardRequest,
+    ) -> Result<T, ClientError> {
         let card = self
-            .http_client
-            .get(format!("{}/cards/{}", self.base_url, request.id))
-            .send()
-            .await?
-            .json::<ApiResult<Card>>()
+            .fetch(
+                &format!("/cards/{}", request.id),
+                &[("select", select_param(&request.select))],
+            )
             .await?;
 
-        Client::parse_response(card)
+        Ok(card.data)
     }
 
     /// Search for one or many cards given a search query.
@@ -148,66 +219,89 @@ impl Client {
     pub async fn search_cards(
         &self,
         request: SearchCardsRequest,
-    ) -> Result<Vec<Card>, ClientError> {
-        let cards = self
-            .http_client
-            .get(format!("{}/cards", self.base_url))
-            .query(&[
-                ("q", request.query),
-                ("page", request.page.map(|p| p.to_string())),
-                ("pageSize", request.page_size.map(|p| p.to_string())),
-                ("orderBy", request.order_by),
-            ])
-            .send()
-            .await?
-            .json::<ApiResult<Vec<Card>>>()
-            .await?;
+    ) -> Result<Page<Card>, ClientError> {
+        schema::check(self.strict, self.search_cards_as(request).await?)
+    }
+
+    /// Search for cards, deserializing each result into any type, such as a struct holding only
+    /// the fields listed in `request.select` or a `serde_json::Value`.
+    /// A [strict](crate::client::ClientBuilder::strict) client doesn't check the result for unknown fields.
+    ///
+    /// https://docs.pokemontcg.io/api-reference/cards/search-cards
+    pub async fn search_cards_as<T: DeserializeOwned>(
+        &self,
+        request: SearchCardsRequest,
+    ) -> Result<Page<T>, ClientError> {
+        self.fetch_page("/cards", &request.into()).await
+    }
 
-        Client::parse_response(cards)
+    /// Stream every card matching a search query, lazily fetching one page at a time.
+    ///
+    /// Paging starts at `request.page` (or the first page) and uses `request.page_size` (or the API default of 250) cards per page.
+    /// Dropping the stream stops any further requests.
+    ///
+    /// ```no_run
+    /// # use futures::{pin_mut, TryStreamExt};
+    /// # use pokemon_tcg_sdk::{card::SearchCardsRequest, client::Client};
+    /// # async fn run() -> Result<(), pokemon_tcg_sdk::errors::ClientError> {
+    /// let client = Client::default();
+    /// let cards = client.stream_cards(SearchCardsRequest::new("set.id:base1"));
+    /// pin_mut!(cards);
+    /// while let Some(card) = cards.try_next().await? {
+    ///     println!("{}", card.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stream_cards(
+        &self,
+        request: SearchCardsRequest,
+    ) -> impl Stream<Item = Result<Card, ClientError>> + '_ {
+        self.paginate("/cards", request.into())
+            .map(move |card| card.and_then(|card| schema::check(self.strict, card)))
     }
 
     /// Get all cards (will take awhile, automatically pages through data)
     pub async fn get_all_cards(&self) -> Result<Vec<Card>, ClientError> {
-        let mut page = 1;
-        let page_size = 250;
-        let mut total_pages: usize = 0;
-        let mut cards: Vec<Card> = vec![];
-
-        loop {
-            let resp = self
-                .http_client
-                .get(format!("{}/cards", self.base_url))
-                .query(&[("page", page)])
-                .send()
-                .await?
-                .json::<ApiResult<Vec<Card>>>()
-                .await?;
-
-            let total_count = if let ApiResult::Ok(ev) = &resp {
-                ev.total_count
-            } else {
-                None
-            };
-
-            match Client::parse_response(resp) {
-                Ok(mut cv) => {
-                    cards.append(&mut cv);
-                    if let Some(tc) = total_count {
-                        total_pages = ((tc / page_size) as f64).ceil() as usize;
-                    }
-
-                    if page > total_pages {
-                        break;
-                    }
-
-                    page += 1;
-                }
-                Err(e) => {
-                    return Err(e);
-                }
-            };
-        }
+        self.get_all_cards_matching(SearchCardsRequest::default())
+            .await
+    }
+
+    /// Get all cards matching a search query, keeping its order (automatically pages through data).
+    ///
+    /// Once the first page reveals the total count, the remaining pages are fetched concurrently,
+    /// up to the limit set by [`ClientBuilder::page_concurrency`](crate::client::ClientBuilder::page_concurrency).
+    pub async fn get_all_cards_matching(
+        &self,
+        request: SearchCardsRequest,
+    ) -> Result<Vec<Card>, ClientError> {
+        schema::check(self.strict, self.fetch_all("/cards", request.into()).await?)
+    }
 
+    /// Get every card of a set (automatically pages through data).
+    ///
+    /// Cards are ordered by collector number, with numbers compared naturally (`2` before `10`).
+    /// In sets that also have unprefixed numbers, subsets with a prefix such as `TG` or `SV` are
+    /// placed last.
+    pub async fn get_set_cards(&self, set_id: &str) -> Result<Vec<Card>, ClientError> {
+        let mut cards = self
+            .get_all_cards_matching(CardQuery::new().set_id(set_id).into())
+            .await?;
+
+        sort_by_collector_number(&mut cards);
         Ok(cards)
     }
 }
+
+/// Orders the cards of a set like in a binder, see [`Client::get_set_cards`]
+pub(crate) fn sort_by_collector_number(cards: &mut [Card]) {
+    let number = |card: &Card| CollectorNumber::from(card.number.as_deref().unwrap_or(""));
+
+    // Prefixes only mark a subset when the main numbering has none, unlike a promo set where
+    // every number is prefixed
+    let has_main_numbers = cards.iter().any(|card| number(card).prefix().is_empty());
+    cards.sort_by_cached_key(|card| {
+        let number = number(card);
+        (has_main_numbers && !number.prefix().is_empty(), number)
+    });
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Ability {
@@ -9,4 +10,7 @@ pub struct Ability {
     /// The type of the ability, such as Ability or Pokémon-Power
     #[serde(alias = "type")]
     pub type_name: String,
+    /// Fields returned by the API that this version of the crate doesn't model.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
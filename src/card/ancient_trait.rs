@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AncientTrait {
@@ -6,4 +7,7 @@ pub struct AncientTrait {
     pub name: String,
     /// The text value of the ancient trait
     pub text: String,
+    /// Fields returned by the API that this version of the crate doesn't model.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
@@ -1,16 +1,76 @@
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::types::EnergyType;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Attack {
     /// The cost of the attack represented by a list of energy types.
-    pub cost: Vec<String>,
+    pub cost: Vec<EnergyType>,
     /// The name of the attack
     pub name: String,
     /// The text or description associated with the attack
     pub text: String,
-    /// The damage amount of the attack
+    /// The damage amount of the attack, such as `30`, `30+`, `20×` or `120-`. See [`Attack::damage()`].
     pub damage: String,
     /// The total cost of the attack. For example, if it costs 2 fire energy, the converted energy cost is simply 2.
     #[serde(alias = "convertedEnergyCost")]
     pub converted_energy_cost: Option<usize>,
+    /// Fields returned by the API that this version of the crate doesn't model.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// The parsed damage of an attack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Damage {
+    /// The damage printed on the card
+    pub base: u32,
+    /// How the text of the attack changes the damage
+    pub modifier: DamageModifier,
+}
+
+/// The symbol printed after the damage of an attack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DamageModifier {
+    /// No symbol, the attack does exactly the base damage.
+    None,
+    /// `+`, the attack can do more than the base damage.
+    Plus,
+    /// `×`, the base damage is multiplied, such as by the number of coin flips that land on heads.
+    Times,
+    /// `-`, the attack can do less than the base damage.
+    Minus,
+}
+
+impl Attack {
+    /// Parses the damage of the attack. `None` for attacks that do no damage.
+    pub fn damage(&self) -> Option<Damage> {
+        let damage = self.damage.trim();
+        let (base, modifier) = match damage.char_indices().last()? {
+            (i, '+') => (&damage[..i], DamageModifier::Plus),
+            (i, '×' | 'x') => (&damage[..i], DamageModifier::Times),
+            (i, '-') => (&damage[..i], DamageModifier::Minus),
+            _ => (damage, DamageModifier::None),
+        };
+
+        Some(Damage {
+            base: base.trim().parse().ok()?,
+            modifier,
+        })
+    }
+}
+
+impl fmt::Display for Damage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let modifier = match self.modifier {
+            DamageModifier::None => "",
+            DamageModifier::Plus => "+",
+            DamageModifier::Times => "×",
+            DamageModifier::Minus => "-",
+        };
+        write!(f, "{}{}", self.base, modifier)
+    }
 }
@@ -1,4 +1,8 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+#[cfg(feature = "chrono")]
+use crate::{date, errors::DateError};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Prices {
@@ -44,6 +48,9 @@ pub struct Prices {
     /// The average sale price over the last 30 days for reverse holos
     #[serde(alias = "reverseHoloAvg30")]
     pub reverse_holo_avg30: Option<f32>,
+    /// Fields returned by the API that this version of the crate doesn't model.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -51,7 +58,26 @@ pub struct CardMarket {
     /// The URL to the cardmarket store page to purchase this card.
     pub url: String,
     /// A date that the price was last updated. In the format of YYYY/MM/DD
+    #[serde(alias = "updatedAt")]
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, deserialize_with = "crate::date::serde_format::optional_date")
+    )]
     pub updated_at: Option<String>,
     /// A hash of price types. All prices are in Euros.
     pub prices: Option<Prices>,
+    /// Fields returned by the API that this version of the crate doesn't model.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[cfg(feature = "chrono")]
+impl CardMarket {
+    /// The date the prices were last updated, parsed from [`CardMarket::updated_at`](#structfield.updated_at).
+    ///
+    /// # Errors
+    /// Fails if the field isn't in the `YYYY/MM/DD` format.
+    pub fn updated_at(&self) -> Result<Option<chrono::NaiveDate>, DateError> {
+        self.updated_at.as_deref().map(date::parse_date).transpose()
+    }
 }
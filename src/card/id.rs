@@ -0,0 +1,148 @@
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+use crate::{errors::InvalidCardId, set::Set};
+
+/// The id of a card, made of the id of its set and its collector number, such as `swsh4-25`.
+///
+/// ```
+/// # use pokemon_tcg_sdk::card::id::CardId;
+/// let id: CardId = "swsh45sv-SV107".parse()?;
+/// assert_eq!(id.set_id, "swsh45sv");
+/// assert_eq!(id.number.prefix(), "SV");
+/// assert_eq!(id.number.number(), Some(107));
+/// # Ok::<(), pokemon_tcg_sdk::errors::InvalidCardId>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CardId {
+    pub set_id: String,
+    pub number: CollectorNumber,
+}
+
+/// The number printed on a card, such as `25`, `25a`, `TG12` or `SWSH001`.
+///
+/// Numbers are ordered naturally, like in a binder: by prefix, then by numeric part (`2` before
+/// `10`), then by suffix. Cards without a prefix come before prefixed cards, such as a Trainer
+/// Gallery.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CollectorNumber {
+    raw: String,
+}
+
+impl CardId {
+    pub fn new(set_id: &str, number: &str) -> Self {
+        CardId {
+            set_id: String::from(set_id),
+            number: CollectorNumber::from(number),
+        }
+    }
+}
+
+impl FromStr for CardId {
+    type Err = InvalidCardId;
+
+    /// Splits an id at its last `-`, as set ids don't contain one
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.rsplit_once('-') {
+            Some((set_id, number)) if !set_id.is_empty() && !number.is_empty() => {
+                Ok(CardId::new(set_id, number))
+            }
+            _ => Err(InvalidCardId(String::from(s))),
+        }
+    }
+}
+
+impl fmt::Display for CardId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.set_id, self.number)
+    }
+}
+
+impl CollectorNumber {
+    /// The letters before the numeric part, such as `TG` in `TG12`. Empty for most cards.
+    pub fn prefix(&self) -> &str {
+        self.parts().0
+    }
+
+    /// The numeric part, such as `12` in `TG12`. `None` if there are no digits.
+    pub fn number(&self) -> Option<u32> {
+        self.parts().1.parse().ok()
+    }
+
+    /// The characters after the numeric part, such as `a` in `25a`. Empty for most cards.
+    pub fn suffix(&self) -> &str {
+        self.parts().2
+    }
+
+    /// The number as printed on the card.
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Whether the card is a secret rare of a set, numbered above its printed total.
+    ///
+    /// Only the numeric part is compared, so prefixed numbers such as the `SWSH` promos or a
+    /// `TG` subset are secret rares only if they are numbered above the printed total as well.
+    pub fn is_secret_rare(&self, set: &Set) -> bool {
+        self.number()
+            .is_some_and(|number| number as usize > set.printed_total)
+    }
+
+    fn parts(&self) -> (&str, &str, &str) {
+        let digits_start = self
+            .raw
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(self.raw.len());
+        let (prefix, rest) = self.raw.split_at(digits_start);
+        let digits_end = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let (digits, suffix) = rest.split_at(digits_end);
+        (prefix, digits, suffix)
+    }
+}
+
+impl From<&str> for CollectorNumber {
+    fn from(value: &str) -> Self {
+        CollectorNumber {
+            raw: String::from(value),
+        }
+    }
+}
+
+impl FromStr for CollectorNumber {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(CollectorNumber::from(s))
+    }
+}
+
+impl fmt::Display for CollectorNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+impl Ord for CollectorNumber {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Numbers without digits go last, and the raw value breaks ties such as `001` and `1`
+        let key = |number: &CollectorNumber| {
+            let value = number.number();
+            (
+                number.prefix().to_uppercase(),
+                value.is_none(),
+                value,
+                number.suffix().to_lowercase(),
+            )
+        };
+        key(self)
+            .cmp(&key(other))
+            .then_with(|| self.raw.cmp(&other.raw))
+    }
+}
+
+impl PartialOrd for CollectorNumber {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
@@ -1,8 +1,7 @@
-use serde::{Deserialize, Serialize};
+//! The legalities of a card, now shared by cards and sets in [`crate::legality`].
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
-pub struct Legality {
-    pub standard: Option<String>,
-    pub expanded: Option<String>,
-    pub unlimited: Option<String>,
-}
+pub use crate::legality::*;
+
+/// The legalities of a card, now [`Legalities`].
+#[deprecated(note = "use `pokemon_tcg_sdk::legality::Legalities` instead")]
+pub type Legality = Legalities;
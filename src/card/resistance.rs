@@ -1,10 +1,25 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::types::EnergyType;
+
+use super::weakness::WeaknessModifier;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Resistance {
     /// The type of resistance, such as Fire or Water.
     #[serde(alias = "type")]
-    pub type_name: String,
-    /// The value of the resistance
+    pub type_name: EnergyType,
+    /// The value of the resistance, such as `-30`. See [`Resistance::modifier()`].
     pub value: String,
+    /// Fields returned by the API that this version of the crate doesn't model.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Resistance {
+    /// Parses the value of the resistance.
+    pub fn modifier(&self) -> Option<WeaknessModifier> {
+        WeaknessModifier::parse(&self.value)
+    }
 }
@@ -1,6 +1,25 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+use crate::types::string_enum;
+#[cfg(feature = "chrono")]
+use crate::{date, errors::DateError};
+
+string_enum! {
+    /// A print variant of a card, the key of its TCGPlayer prices.
+    pub enum PrintVariant {
+        Normal => "normal",
+        Holofoil => "holofoil",
+        ReverseHolofoil => "reverseHolofoil",
+        FirstEditionNormal => "1stEditionNormal",
+        FirstEditionHolofoil => "1stEditionHolofoil",
+        UnlimitedHolofoil => "unlimitedHolofoil",
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
 pub struct Prices {
     /// The low price of the card
     pub low: Option<f32>,
@@ -13,6 +32,50 @@ pub struct Prices {
     /// The direct low price of the card
     #[serde(alias = "directLow")]
     pub direct_low: Option<f32>,
+    /// Fields returned by the API that this version of the crate doesn't model.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// The prices of every print variant of a card that is sold on TCGPlayer.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct TcgPlayerPrices(BTreeMap<PrintVariant, Prices>);
+
+impl TcgPlayerPrices {
+    /// The prices of a print variant, if it is sold.
+    pub fn get(&self, variant: &PrintVariant) -> Option<&Prices> {
+        self.0.get(variant)
+    }
+
+    /// The market price of a print variant.
+    pub fn market_price(&self, variant: PrintVariant) -> Option<f32> {
+        self.get(&variant).and_then(|prices| prices.market)
+    }
+
+    /// The print variants that have prices.
+    pub fn variants(&self) -> impl Iterator<Item = &PrintVariant> {
+        self.0.keys()
+    }
+
+    /// The prices of every print variant.
+    pub fn iter(&self) -> impl Iterator<Item = (&PrintVariant, &Prices)> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromIterator<(PrintVariant, Prices)> for TcgPlayerPrices {
+    fn from_iter<I: IntoIterator<Item = (PrintVariant, Prices)>>(iter: I) -> Self {
+        TcgPlayerPrices(iter.into_iter().collect())
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -21,7 +84,25 @@ pub struct TcgPlayer {
     pub url: String,
     /// A date that the price was last updated. In the format of YYYY/MM/DD
     #[serde(alias = "updatedAt")]
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, deserialize_with = "crate::date::serde_format::optional_date")
+    )]
     pub updated_at: Option<String>,
-    /// A hash of price types. All prices are in US Dollars. See below for possible values.
-    pub prices: Option<Prices>,
+    /// The prices of each print variant, such as normal, holofoil or reverse holofoil. All prices are in US Dollars.
+    pub prices: Option<TcgPlayerPrices>,
+    /// Fields returned by the API that this version of the crate doesn't model.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[cfg(feature = "chrono")]
+impl TcgPlayer {
+    /// The date the prices were last updated, parsed from [`TcgPlayer::updated_at`](#structfield.updated_at).
+    ///
+    /// # Errors
+    /// Fails if the field isn't in the `YYYY/MM/DD` format.
+    pub fn updated_at(&self) -> Result<Option<chrono::NaiveDate>, DateError> {
+        self.updated_at.as_deref().map(date::parse_date).transpose()
+    }
 }
@@ -1,10 +1,62 @@
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::types::EnergyType;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Weakness {
     /// The type of weakness, such as Fire or Water.
     #[serde(alias = "type")]
-    pub type_name: String,
-    /// The value of the weakness
+    pub type_name: EnergyType,
+    /// The value of the weakness, such as `×2` or `+20`. See [`Weakness::modifier()`].
     pub value: String,
+    /// Fields returned by the API that this version of the crate doesn't model.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// How a weakness or resistance changes the damage of an attack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WeaknessModifier {
+    /// The damage is multiplied, such as `×2`.
+    Multiply(u32),
+    /// The amount is added to the damage, such as `+20`, or subtracted when negative, such as `-30`.
+    Add(i32),
+}
+
+impl Weakness {
+    /// Parses the value of the weakness.
+    pub fn modifier(&self) -> Option<WeaknessModifier> {
+        WeaknessModifier::parse(&self.value)
+    }
+}
+
+impl WeaknessModifier {
+    /// Applies the modifier to the damage of an attack. Damage never goes below zero.
+    pub fn apply(&self, damage: u32) -> u32 {
+        match *self {
+            WeaknessModifier::Multiply(factor) => damage.saturating_mul(factor),
+            WeaknessModifier::Add(amount) => damage.saturating_add_signed(amount),
+        }
+    }
+
+    pub(crate) fn parse(value: &str) -> Option<WeaknessModifier> {
+        let value = value.trim();
+        if let Some(factor) = value.strip_prefix('×').or_else(|| value.strip_prefix('x')) {
+            return factor.trim().parse().ok().map(WeaknessModifier::Multiply);
+        }
+
+        value.parse().ok().map(WeaknessModifier::Add)
+    }
+}
+
+impl fmt::Display for WeaknessModifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WeaknessModifier::Multiply(factor) => write!(f, "×{}", factor),
+            WeaknessModifier::Add(amount) => write!(f, "{:+}", amount),
+        }
+    }
 }
@@ -1,73 +1,470 @@
-use crate::errors::{ClientError, ErrorEnvelope};
-use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
+use crate::{
+    cache::{self, CacheConfig, DiskCache, Endpoint, ResponseCache, StoredResponse},
+    errors::{ClientError, ErrorEnvelope},
+    page::Page,
+    rate_limit::{RateLimit, RateLimiter},
+    retry::{self, RetryPolicy},
+    time,
+    transport::{self, SearchParams},
+};
+use bytes::Bytes;
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+const DEFAULT_BASE_URL: &str = "https://api.pokemontcg.io/v2";
+pub(crate) const DEFAULT_PAGE_CONCURRENCY: usize = 4;
+
+/// The client for the Pokémon TCG API.
+///
+/// Cloning a client is cheap: clones share the connection pool, the rate limiter and the response cache.
+#[derive(Debug, Clone)]
 pub struct Client {
     pub(super) base_url: String,
     pub(super) http_client: reqwest::Client,
+    /// Headers sent with every request (API key, user agent, custom defaults)
+    pub(super) headers: HeaderMap,
+    pub(super) retry_policy: RetryPolicy,
+    pub(super) rate_limiter: Option<RateLimiter>,
+    /// How many pages `get_all_*` methods fetch at the same time
+    pub(super) page_concurrency: usize,
+    pub(super) cache: Option<ResponseCache>,
+    pub(super) disk_cache: Option<DiskCache>,
+    pub(super) strict: bool,
 }
 
 impl Client {
     /// Constructs a new client
-    /// 
+    ///
     /// # Errors
-    /// This method fails if the API key is invalid ("\n" etc.) 
+    /// This method fails if the API key is invalid ("\n" etc.)
     /// or if a TLS backend cannot be initialized, or the resolver
     /// cannot load the system configuration.
     pub fn new(api_key: Option<&str>) -> Result<Self, ClientError> {
-        Ok(Client {
-            base_url: String::from(r#"https://api.pokemontcg.io/v2"#),
-            http_client: Client::get_http_client(api_key)?,
-        })
+        let mut builder = ClientBuilder::new();
+        if let Some(key) = api_key {
+            builder = builder.api_key(key);
+        }
+
+        builder.build()
     }
 
     /// Constructs a client with a different base url than the default for the API.
     ///
     /// # Errors
-    /// This method fails if the API key is invalid ("\n" etc.) 
+    /// This method fails if the API key is invalid ("\n" etc.)
     /// or if a TLS backend cannot be initialized, or the resolver
     /// cannot load the system configuration.
     pub fn with_base_url(base_url: &str, api_key: Option<&str>) -> Result<Self, ClientError> {
-        Ok(Client {
-            base_url: String::from(base_url),
-            http_client: Client::get_http_client(api_key)?,
-        })
+        let mut builder = ClientBuilder::new().base_url(base_url);
+        if let Some(key) = api_key {
+            builder = builder.api_key(key);
+        }
+
+        builder.build()
     }
 
     /// Constructs a client with an API key that will be passed on every request.
-    /// 
+    ///
     /// # Errors
-    /// This method fails if the API key is invalid ("\n" etc.) 
+    /// This method fails if the API key is invalid ("\n" etc.)
     /// or if a TLS backend cannot be initialized, or the resolver
     /// cannot load the system configuration.
     pub fn with_api_key(api_key: &str) -> Result<Self, ClientError> {
         Client::new(Some(api_key))
     }
 
-    /// Builds the reqwest http client that will be used for all API requests
-    pub(super) fn get_http_client(api_key: Option<&str>) -> Result<reqwest::Client, ClientError> {
-        let mut http_client = reqwest::Client::builder();
-        if let Some(key) = api_key {
-            let mut headers = reqwest::header::HeaderMap::new();
-            headers.insert("X-Api-Key", reqwest::header::HeaderValue::from_str(key)?);
-            http_client = http_client.default_headers(headers);
+    /// Returns a builder to configure timeouts, headers, proxies and more.
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
+    /// Fetches a path relative to the base url and decodes the data envelope,
+    /// retrying failed attempts according to the retry policy of the client.
+    /// Successful responses are served from and stored in the response cache, if one is configured,
+    /// and revalidated against the disk cache.
+    pub(super) async fn fetch<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, Option<String>)],
+    ) -> Result<DataEnvelope<T>, ClientError> {
+        let url = transport::url(&self.base_url, path, query)?;
+        let key = url.to_string();
+        let mut request = self
+            .http_client
+            .get(url)
+            .headers(self.headers.clone())
+            .build()?;
+        let cache = self.cache.as_ref().zip(Endpoint::from_path(path));
+
+        if let Some((cache, _)) = cache {
+            if let Some(body) = cache.get(&key) {
+                return transport::decode(&body);
+            }
+        }
+
+        let stored = match self.disk_cache.clone() {
+            Some(disk) => {
+                let key = key.clone();
+                cache::unblock(move || disk.load(&key)).await
+            }
+            None => None,
+        };
+        if let Some(stored) = &stored {
+            stored.add_validators(request.headers_mut());
+        }
+
+        let mut attempt = 1;
+        let body = loop {
+            let (result, retry_after) = self.fetch_once(&request, stored.as_ref()).await;
+            match transport::retry_delay(&self.retry_policy, &result, attempt, retry_after) {
+                Some(delay) => {
+                    time::sleep(delay).await;
+                    attempt += 1;
+                }
+                None => break result?,
+            }
+        };
+
+        let envelope = transport::decode(&body)?;
+        if let Some((cache, endpoint)) = cache {
+            cache.insert(&key, endpoint, body);
         }
 
-        http_client.build().map_err(|e| e.into())
+        Ok(envelope)
     }
 
-    /// Parses the response from the API into either a generic data envelope ({data: T}) or 
-    /// an error envelope ({error: ...}) and returns T if successful
-    pub(super) fn parse_response<T>(result: ApiResult<T>) -> Result<T, ClientError> {
-        match result {
-            ApiResult::Ok(v) => Ok(v.data),
-            ApiResult::Err(e) => Err(e.into()),
+    /// Fetches a single page of a search endpoint.
+    pub(super) async fn fetch_page<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        params: &SearchParams,
+    ) -> Result<Page<T>, ClientError> {
+        let resp = self.fetch::<Vec<T>>(path, &params.to_query()).await?;
+
+        Ok(params.page_of(resp))
+    }
+
+    /// Fetches every page of a search endpoint. The first page tells how many pages there are,
+    /// the others are then fetched concurrently while keeping their order.
+    pub(super) async fn fetch_all<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        params: SearchParams,
+    ) -> Result<Vec<T>, ClientError> {
+        let params = params.first_page();
+        let first = self.fetch_page::<T>(path, &params).await?;
+        let last_page = u16::try_from(first.total_pages()).unwrap_or(u16::MAX);
+        let mut items = first.data;
+        if first.page >= last_page {
+            return Ok(items);
+        }
+
+        let pages: Vec<Vec<T>> = stream::iter(first.page + 1..=last_page)
+            .map(|page| {
+                let params = params.with_page(page);
+                async move { self.fetch_page::<T>(path, &params).await }
+            })
+            .buffered(self.page_concurrency)
+            .map_ok(|page| page.data)
+            .try_collect()
+            .await?;
+
+        items.extend(pages.into_iter().flatten());
+        Ok(items)
+    }
+
+    /// Lazily pages through a search endpoint. The next page is only requested
+    /// once every item of the previous page has been consumed.
+    pub(super) fn paginate<T: DeserializeOwned>(
+        &self,
+        path: &'static str,
+        params: SearchParams,
+    ) -> impl Stream<Item = Result<T, ClientError>> + '_ {
+        stream::try_unfold(Some(params.first_page()), move |params| async move {
+            let params = match params {
+                Some(params) => params,
+                None => return Ok::<_, ClientError>(None),
+            };
+
+            let page = self.fetch_page::<T>(path, &params).await?;
+            let next = params.next_page(&page);
+
+            Ok(Some((page.data, next)))
+        })
+        .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+        .try_flatten()
+    }
+
+    /// Makes a single attempt at a request, returning the body of a successful response
+    /// and the `Retry-After` delay the API asked for, if any.
+    async fn fetch_once(
+        &self,
+        request: &reqwest::Request,
+        stored: Option<&StoredResponse>,
+    ) -> (Result<Bytes, ClientError>, Option<Duration>) {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire().await;
         }
+
+        // GET requests have no body, so they can always be cloned
+        let request = request.try_clone().expect("GET requests can be cloned");
+        let url = request.url().clone();
+        let response = match self.http_client.execute(request).await {
+            Ok(response) => response,
+            Err(e) => return (Err(e.into()), None),
+        };
+
+        let retry_after = retry::retry_after(response.headers());
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = match response.bytes().await {
+            Ok(body) => transport::read_response(stored, status, body),
+            Err(e) => Err(e.into()),
+        };
+        let result = match (body, &self.disk_cache) {
+            (Ok(body), Some(disk)) => {
+                let (disk, stored, url) = (disk.clone(), stored.cloned(), String::from(url));
+                Ok(
+                    cache::unblock(move || body.save(Some(&disk), stored.as_ref(), &url, &headers))
+                        .await,
+                )
+            }
+            (Ok(body), None) => Ok(body.save(None, None, url.as_str(), &headers)),
+            (Err(e), _) => Err(e),
+        };
+
+        (result, retry_after)
+    }
+
+    /// The response cache of the client, if one was configured with [`ClientBuilder::cache`].
+    pub fn cache(&self) -> Option<&ResponseCache> {
+        self.cache.as_ref()
+    }
+
+    /// The disk cache of the client, if one was configured with [`ClientBuilder::disk_cache`].
+    pub fn disk_cache(&self) -> Option<&DiskCache> {
+        self.disk_cache.as_ref()
+    }
+}
+
+/// Configures and constructs a [`Client`].
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use pokemon_tcg_sdk::client::Client;
+/// let client = Client::builder()
+///     .api_key("YOUR_API_KEY")
+///     .connect_timeout(Duration::from_secs(5))
+///     .timeout(Duration::from_secs(30))
+///     .user_agent("my-service/1.0")
+///     .build()?;
+/// # Ok::<(), pokemon_tcg_sdk::errors::ClientError>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct ClientBuilder {
+    pub(crate) base_url: Option<String>,
+    pub(crate) api_key: Option<String>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) connect_timeout: Option<Duration>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) user_agent: Option<String>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) proxy: Option<reqwest::Proxy>,
+    pub(crate) default_headers: HeaderMap,
+    pub(crate) http_client: Option<reqwest::Client>,
+    pub(crate) retry_policy: Option<RetryPolicy>,
+    pub(crate) rate_limit: Option<RateLimit>,
+    pub(crate) page_concurrency: Option<usize>,
+    pub(crate) cache: Option<CacheConfig>,
+    pub(crate) disk_cache: Option<DiskCache>,
+    pub(crate) strict: bool,
+}
+
+impl ClientBuilder {
+    /// Constructs a builder with the default configuration, same as [`Client::builder`].
+    pub fn new() -> Self {
+        ClientBuilder::default()
+    }
+
+    /// Sets the base url of the API. Defaults to `https://api.pokemontcg.io/v2`.
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = Some(String::from(base_url));
+        self
+    }
+
+    /// Sets an API key that will be passed on every request.
+    pub fn api_key(mut self, api_key: &str) -> Self {
+        self.api_key = Some(String::from(api_key));
+        self
+    }
+
+    /// Sets a timeout for only the connect phase of a request.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets a timeout for the whole request, from connecting until the response body has been read.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the `User-Agent` header sent with every request.
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(String::from(user_agent));
+        self
+    }
+
+    /// Routes all requests through the given proxy.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Sets headers that will be sent with every request.
+    /// The API key and user agent take precedence over headers of the same name.
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers = headers;
+        self
+    }
+
+    /// Uses an already configured reqwest client for all requests.
+    ///
+    /// Transport options of this builder (timeouts and proxy) are ignored in that case and must be
+    /// configured on the supplied client instead. Headers are still applied to every request.
+    pub fn http_client(mut self, http_client: reqwest::Client) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    /// Retries failed requests according to the given policy. By default, requests are not retried.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Throttles outgoing requests, including retries, to the given rate.
+    /// The limit is shared by every endpoint and by all clones of the built client.
+    pub fn rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    /// Sets how many pages `get_all_*` methods fetch at the same time. Defaults to 4, zero is treated as one.
+    pub fn page_concurrency(mut self, page_concurrency: usize) -> Self {
+        self.page_concurrency = Some(page_concurrency.max(1));
+        self
+    }
+
+    /// Caches successful responses in memory, keyed by their URL and query parameters.
+    /// The cache is shared by all clones of the built client.
+    pub fn cache(mut self, config: CacheConfig) -> Self {
+        self.cache = Some(config);
+        self
+    }
+
+    /// Stores responses on disk and revalidates them with conditional requests,
+    /// so that unchanged data isn't downloaded again after a restart.
+    /// Not available in the browser, which has no file system.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn disk_cache(mut self, disk_cache: DiskCache) -> Self {
+        self.disk_cache = Some(disk_cache);
+        self
+    }
+
+    /// Fails requests for cards and sets with [`ClientError::UnknownFields`] when the API returns
+    /// fields the models don't know about, to catch changes to the API early. Off by default, in
+    /// which case unknown fields are kept in the `extra` map of each model.
+    ///
+    /// The check applies to every response, including those served from the caches. The `_as`
+    /// methods, which decode into types of the caller's choosing, are exempt.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Constructs the client.
+    ///
+    /// # Errors
+    /// This method fails if the API key or user agent are invalid header values ("\n" etc.)
+    /// or if a TLS backend cannot be initialized, or the resolver
+    /// cannot load the system configuration.
+    pub fn build(self) -> Result<Client, ClientError> {
+        let headers = self.headers()?;
+        let base_url = self.base_url_or_default();
+
+        let http_client = match self.http_client {
+            Some(http_client) => http_client,
+            None => {
+                #[allow(unused_mut)]
+                let mut builder = reqwest::Client::builder();
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    if let Some(timeout) = self.connect_timeout {
+                        builder = builder.connect_timeout(timeout);
+                    }
+                    if let Some(timeout) = self.timeout {
+                        builder = builder.timeout(timeout);
+                    }
+                    if let Some(proxy) = self.proxy {
+                        builder = builder.proxy(proxy);
+                    }
+                }
+                builder.build()?
+            }
+        };
+
+        Ok(Client {
+            base_url,
+            http_client,
+            headers,
+            retry_policy: self.retry_policy.unwrap_or_else(RetryPolicy::none),
+            rate_limiter: self.rate_limit.map(RateLimiter::new),
+            page_concurrency: self.page_concurrency.unwrap_or(DEFAULT_PAGE_CONCURRENCY),
+            cache: self.cache.map(ResponseCache::new),
+            disk_cache: self.disk_cache,
+            strict: self.strict,
+        })
+    }
+}
+
+impl ClientBuilder {
+    /// The headers sent with every request: the default headers, user agent and API key
+    pub(crate) fn headers(&self) -> Result<HeaderMap, ClientError> {
+        let mut headers = self.default_headers.clone();
+        if let Some(user_agent) = &self.user_agent {
+            let value = HeaderValue::from_str(user_agent).map_err(ClientError::InvalidUserAgent)?;
+            headers.insert(USER_AGENT, value);
+        }
+        if let Some(key) = &self.api_key {
+            headers.insert("X-Api-Key", HeaderValue::from_str(key)?);
+        }
+
+        Ok(headers)
+    }
+
+    pub(crate) fn base_url_or_default(&self) -> String {
+        self.base_url
+            .clone()
+            .unwrap_or_else(|| String::from(DEFAULT_BASE_URL))
     }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DataEnvelope<T> {
     pub data: T,
+    pub page: Option<usize>,
+    #[serde(alias = "pageSize")]
+    pub page_size: Option<usize>,
+    pub count: Option<usize>,
     #[serde(alias = "totalCount")]
     pub total_count: Option<usize>,
 }
@@ -81,7 +478,7 @@ pub enum ApiResult<T> {
 
 impl Default for Client {
     /// Constructs a basic client with no API Key using the default URL.
-    /// 
+    ///
     /// # Panics
     /// This method will panic if the construction of the reqwest http client fails,
     /// if a TLS backend cannot be initialized, or the resolver
@@ -0,0 +1,117 @@
+//! Loads the [pokemon-tcg-data](https://github.com/PokemonTCG/pokemon-tcg-data) JSON dump,
+//! for working without access to the API.
+//!
+//! The dump has one file listing every set, `sets/<language>.json`, and one file per set with its
+//! cards, `cards/<language>/<set id>.json`. A [mirror snapshot](crate::mirror::Snapshot) uses the
+//! same file names and can be loaded as well, as the models read both its snake_case fields and the camelCase
+//! fields of the dump.
+//!
+//! ```no_run
+//! # use pokemon_tcg_sdk::dataset::Dataset;
+//! let dataset = Dataset::load("./pokemon-tcg-data")?;
+//! let base_set = dataset.cards_of("base1").count();
+//! # Ok::<(), pokemon_tcg_sdk::errors::DatasetError>(())
+//! ```
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::{card::Card, errors::DatasetError, set::Set};
+
+/// Every set and card of a dump.
+#[derive(Debug, Clone, Default)]
+pub struct Dataset {
+    /// The sets, in the order of the sets file.
+    pub sets: Vec<Set>,
+    /// The cards, set by set in the order of the sets file.
+    pub cards: Vec<Card>,
+}
+
+impl Dataset {
+    /// Loads the English data of a dump.
+    ///
+    /// # Errors
+    /// Fails if a file can't be read or doesn't match the models.
+    pub fn load(dir: impl AsRef<Path>) -> Result<Dataset, DatasetError> {
+        Dataset::load_language(dir, "en")
+    }
+
+    /// Loads the data of a dump for a language, such as `en`.
+    ///
+    /// The dump omits the set embedded in each card, so it is filled in from the sets file.
+    /// Sets without a cards file are loaded without cards.
+    ///
+    /// # Errors
+    /// Fails if a file can't be read or doesn't match the models.
+    pub fn load_language(dir: impl AsRef<Path>, language: &str) -> Result<Dataset, DatasetError> {
+        let dir = dir.as_ref();
+        let sets: Vec<Set> = read_json(&dir.join("sets").join(format!("{}.json", language)))?;
+
+        let mut cards = vec![];
+        for set in &sets {
+            let path = dir
+                .join("cards")
+                .join(language)
+                .join(format!("{}.json", set.id));
+            let values: Vec<Value> = match read_json(&path) {
+                Ok(values) => values,
+                Err(DatasetError::Io { source, .. })
+                    if source.kind() == io::ErrorKind::NotFound =>
+                {
+                    continue
+                }
+                Err(e) => return Err(e),
+            };
+
+            let set_value = serde_json::to_value(set).map_err(|source| DatasetError::Json {
+                path: path.clone(),
+                source,
+            })?;
+            for mut value in values {
+                if let Value::Object(card) = &mut value {
+                    card.entry("set").or_insert_with(|| set_value.clone());
+                }
+                let card = serde_json::from_value(value).map_err(|source| DatasetError::Json {
+                    path: path.clone(),
+                    source,
+                })?;
+                cards.push(card);
+            }
+        }
+
+        Ok(Dataset { sets, cards })
+    }
+
+    /// Finds a set by its id.
+    pub fn set(&self, id: &str) -> Option<&Set> {
+        self.sets.iter().find(|set| set.id == id)
+    }
+
+    /// Finds a card by its id.
+    pub fn card(&self, id: &str) -> Option<&Card> {
+        self.cards.iter().find(|card| card.id == id)
+    }
+
+    /// The cards of a set.
+    pub fn cards_of<'a>(&'a self, set_id: &'a str) -> impl Iterator<Item = &'a Card> + 'a {
+        self.cards.iter().filter(move |card| card.set.id == set_id)
+    }
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, DatasetError> {
+    let path_buf = || PathBuf::from(path);
+    let contents = fs::read(path).map_err(|source| DatasetError::Io {
+        path: path_buf(),
+        source,
+    })?;
+
+    serde_json::from_slice(&contents).map_err(|source| DatasetError::Json {
+        path: path_buf(),
+        source,
+    })
+}
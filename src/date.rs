@@ -0,0 +1,93 @@
+//! Parses the dates and timestamps of the models, available with the `chrono` feature.
+//!
+//! The API formats dates as `YYYY/MM/DD` and timestamps as `YYYY/MM/DD HH:MM:SS`. The models keep
+//! them as the strings returned by the API, so that enabling the feature doesn't change their
+//! types. With the feature, they are checked when a model is deserialized, failing with a
+//! [`DateError`] on malformed values, and accessors such as [`Set::release_date`] return them as
+//! `chrono` types.
+//!
+//! ```
+//! # use pokemon_tcg_sdk::date;
+//! let released = date::parse_date("1999/01/09")?;
+//! assert_eq!(date::format_date(&released), "1999/01/09");
+//! # Ok::<(), pokemon_tcg_sdk::errors::DateError>(())
+//! ```
+//!
+//! [`Set::release_date`]: crate::set::Set::release_date()
+
+use chrono::{NaiveDate, NaiveDateTime};
+
+use crate::errors::DateError;
+
+/// The format of dates, such as the release date of a set
+pub const DATE_FORMAT: &str = "YYYY/MM/DD";
+
+/// The format of timestamps, such as the time a set was last updated
+pub const DATE_TIME_FORMAT: &str = "YYYY/MM/DD HH:MM:SS";
+
+const CHRONO_DATE_FORMAT: &str = "%Y/%m/%d";
+const CHRONO_DATE_TIME_FORMAT: &str = "%Y/%m/%d %H:%M:%S";
+
+/// Parses a date in the `YYYY/MM/DD` format of the API.
+///
+/// # Errors
+/// Fails if the value is not a valid date.
+pub fn parse_date(value: &str) -> Result<NaiveDate, DateError> {
+    NaiveDate::parse_from_str(value, CHRONO_DATE_FORMAT).map_err(|_| DateError {
+        value: String::from(value),
+        format: DATE_FORMAT,
+    })
+}
+
+/// Parses a timestamp in the `YYYY/MM/DD HH:MM:SS` format of the API.
+///
+/// # Errors
+/// Fails if the value is not a valid timestamp.
+pub fn parse_date_time(value: &str) -> Result<NaiveDateTime, DateError> {
+    NaiveDateTime::parse_from_str(value, CHRONO_DATE_TIME_FORMAT).map_err(|_| DateError {
+        value: String::from(value),
+        format: DATE_TIME_FORMAT,
+    })
+}
+
+/// Formats a date the way the API does.
+pub fn format_date(date: &NaiveDate) -> String {
+    date.format(CHRONO_DATE_FORMAT).to_string()
+}
+
+/// Formats a timestamp the way the API does.
+pub fn format_date_time(date_time: &NaiveDateTime) -> String {
+    date_time.format(CHRONO_DATE_TIME_FORMAT).to_string()
+}
+
+/// Serde adapters that check the date fields of the models while keeping them as strings,
+/// used with `#[serde(deserialize_with = "...")]`
+pub(crate) mod serde_format {
+    use serde::{de::Error, Deserialize, Deserializer};
+
+    use super::{parse_date, parse_date_time};
+
+    pub(crate) fn date<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        parse_date(&value).map_err(D::Error::custom)?;
+        Ok(value)
+    }
+
+    pub(crate) fn optional_date<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<String>, D::Error> {
+        let value = Option::<String>::deserialize(deserializer)?;
+        if let Some(value) = &value {
+            parse_date(value).map_err(D::Error::custom)?;
+        }
+        Ok(value)
+    }
+
+    pub(crate) fn date_time<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<String, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        parse_date_time(&value).map_err(D::Error::custom)?;
+        Ok(value)
+    }
+}
@@ -21,16 +21,25 @@ pub enum ClientError {
     /// Something went wrong on our end.
     #[error("Server Error: {}", .0.error.message)]
     ServerError(ErrorEnvelope),
-    /// Error parsing the response body
-    #[error("Failed to decode the response body")]
-    DecodeFailed(#[source] reqwest::Error),
+    /// Error parsing the response body, with the path of the field that failed such as
+    /// `data.attacks[0].convertedEnergyCost`
+    #[error("Failed to decode the response body at `{}`", .0.path())]
+    DecodeFailed(#[source] serde_path_to_error::Error<serde_json::Error>),
     /// Error occurred before the body could be decoded
     #[error("An error occurred while attempting to make a request.")]
     RequestError(#[source] reqwest::Error),
+    /// The base url and path of a request don't form a valid URL
+    #[error("The request URL is invalid.")]
+    InvalidUrl(#[source] url::ParseError),
     #[error("An error occurred while constructing the client.")]
     ConstructionFailed(#[source] reqwest::Error),
     #[error("The API key is invalid.")]
     InvalidApiKey(#[source] reqwest::header::InvalidHeaderValue),
+    #[error("The user agent is invalid.")]
+    InvalidUserAgent(#[source] reqwest::header::InvalidHeaderValue),
+    /// A strict client received fields the models don't know about, listed by their path.
+    #[error("The response has unknown fields: {}", .0.join(", "))]
+    UnknownFields(Vec<String>),
 }
 
 /// The root response body for an error
@@ -46,6 +55,34 @@ pub struct ApiError {
     pub code: usize,
 }
 
+impl ClientError {
+    /// Whether the error is likely temporary, so that repeating the request may succeed:
+    /// rate limiting, server errors, timeouts and connection failures.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            ClientError::TooManyRequests(_) | ClientError::ServerError(_) => true,
+            #[cfg(not(target_arch = "wasm32"))]
+            ClientError::RequestError(e) => e.is_timeout() || e.is_connect(),
+            #[cfg(target_arch = "wasm32")]
+            ClientError::RequestError(e) => e.is_timeout(),
+            _ => false,
+        }
+    }
+}
+
+impl ErrorEnvelope {
+    /// Builds an error envelope for responses whose body is not a valid error envelope,
+    /// such as errors returned by a proxy in front of the API.
+    pub(crate) fn from_status(status: reqwest::StatusCode) -> Self {
+        ErrorEnvelope {
+            error: ApiError {
+                message: String::from(status.canonical_reason().unwrap_or("Unknown error")),
+                code: status.as_u16() as usize,
+            },
+        }
+    }
+}
+
 impl From<ErrorEnvelope> for ClientError {
     fn from(e: ErrorEnvelope) -> Self {
         match e.error.code {
@@ -75,3 +112,52 @@ impl From<reqwest::header::InvalidHeaderValue> for ClientError {
         ClientError::InvalidApiKey(e)
     }
 }
+
+/// An error reading or syncing a local [mirror](crate::mirror).
+#[derive(Error, Debug)]
+pub enum MirrorError {
+    /// Fetching from the API failed
+    #[error(transparent)]
+    Client(#[from] ClientError),
+    #[error("Failed to read or write the mirror")]
+    Io(#[from] std::io::Error),
+    #[error("A mirror file is not valid JSON")]
+    Json(#[from] serde_json::Error),
+    /// The mirror was written by an incompatible version of this crate
+    #[error("Unsupported mirror format version {0}")]
+    UnsupportedFormat(u32),
+}
+
+/// An error loading a [dataset](crate::dataset) from disk.
+#[derive(Error, Debug)]
+pub enum DatasetError {
+    #[error("Failed to read {}", .path.display())]
+    Io {
+        path: std::path::PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    /// A file is not valid JSON or doesn't match the models
+    #[error("Failed to decode {}", .path.display())]
+    Json {
+        path: std::path::PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+/// A date or timestamp that doesn't match the format used by the API.
+#[cfg(feature = "chrono")]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid date `{value}`, expected the format {format}")]
+pub struct DateError {
+    /// The malformed value
+    pub value: String,
+    /// The expected format, such as `YYYY/MM/DD`
+    pub format: &'static str,
+}
+
+/// A card id that isn't made of a set id and a collector number, such as `swsh4-25`.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid card id `{0}`, expected <set id>-<number>")]
+pub struct InvalidCardId(pub String);
@@ -0,0 +1,102 @@
+//! Whether cards and sets can be played in each format of the Pokémon TCG.
+//!
+//! ```
+//! # use pokemon_tcg_sdk::legality::{Format, Legalities, LegalityStatus};
+//! let legalities: Legalities =
+//!     serde_json::from_str(r#"{ "unlimited": "Legal", "expanded": "Banned" }"#).unwrap();
+//! assert!(legalities.is_legal_in(Format::Unlimited));
+//! assert!(!legalities.is_legal_in(Format::Standard));
+//! assert_eq!(legalities.banned_formats(), vec![Format::Expanded]);
+//! ```
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::types::string_enum;
+
+/// A format of play.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Format {
+    Standard,
+    Expanded,
+    Unlimited,
+}
+
+string_enum! {
+    /// Whether a card or set can be played in a format.
+    pub enum LegalityStatus {
+        Legal => "Legal",
+        Banned => "Banned",
+    }
+}
+
+/// The status of a card or set in each format. A format is absent when the card or set is not
+/// legal in it, and present when it is legal or banned.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Legalities {
+    pub standard: Option<LegalityStatus>,
+    pub expanded: Option<LegalityStatus>,
+    pub unlimited: Option<LegalityStatus>,
+    /// Fields returned by the API that this version of the crate doesn't model.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Format {
+    /// Every format, from the most to the least restrictive.
+    pub const ALL: [Format; 3] = [Format::Standard, Format::Expanded, Format::Unlimited];
+
+    /// The name of the format in the API, such as `standard`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Format::Standard => "standard",
+            Format::Expanded => "expanded",
+            Format::Unlimited => "unlimited",
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Legalities {
+    /// The status in a format, `None` if the card or set is not legal in it.
+    pub fn status(&self, format: Format) -> Option<&LegalityStatus> {
+        match format {
+            Format::Standard => self.standard.as_ref(),
+            Format::Expanded => self.expanded.as_ref(),
+            Format::Unlimited => self.unlimited.as_ref(),
+        }
+    }
+
+    /// Whether the card or set can be played in a format.
+    pub fn is_legal_in(&self, format: Format) -> bool {
+        self.status(format) == Some(&LegalityStatus::Legal)
+    }
+
+    /// Whether the card or set is banned from a format.
+    pub fn is_banned_in(&self, format: Format) -> bool {
+        self.status(format) == Some(&LegalityStatus::Banned)
+    }
+
+    /// The formats the card or set can be played in.
+    pub fn legal_formats(&self) -> Vec<Format> {
+        Format::ALL
+            .into_iter()
+            .filter(|format| self.is_legal_in(*format))
+            .collect()
+    }
+
+    /// The formats the card or set is banned from.
+    pub fn banned_formats(&self) -> Vec<Format> {
+        Format::ALL
+            .into_iter()
+            .filter(|format| self.is_banned_in(*format))
+            .collect()
+    }
+}
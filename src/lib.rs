@@ -1,15 +1,55 @@
+#[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+pub mod blocking;
+pub mod cache;
 pub mod card;
 pub mod client;
+pub mod dataset;
+#[cfg(feature = "chrono")]
+pub mod date;
 pub mod errors;
+pub mod legality;
+pub mod mirror;
+pub mod page;
+pub mod query;
+pub mod rate_limit;
+pub mod retry;
+pub mod schema;
 pub mod set;
+pub mod source;
+mod time;
+mod transport;
 pub mod types;
 
 #[cfg(test)]
 mod tests {
+    use crate::cache::{CacheConfig, DiskCache, Endpoint};
+    use crate::card::attack::{Attack, Damage, DamageModifier};
+    use crate::card::id::{CardId, CollectorNumber};
+    use crate::card::resistance::Resistance;
+    use crate::card::tcgplayer::{PrintVariant, TcgPlayer};
+    use crate::card::weakness::{Weakness, WeaknessModifier};
     use crate::card::{Card, SearchCardsRequest};
     use crate::client::{ApiResult, DataEnvelope};
-    use crate::set::{GetSetRequest, SearchSetsRequest};
+    use crate::dataset::Dataset;
+    use crate::errors::{ClientError, DatasetError};
+    use crate::legality::{Format, Legalities, LegalityStatus};
+    use crate::mirror::Mirror;
+    use crate::page::Page;
+    use crate::query::{
+        CardField, CardQuery, CardSort, Match, OrderBy, Query, SetField, SetQuery, SetSort,
+    };
+    use crate::rate_limit::RateLimit;
+    use crate::retry::RetryPolicy;
+    use crate::schema::UnknownFields;
+    use crate::set::{GetSetRequest, SearchSetsRequest, Set};
+    use crate::source::{CardSource, MemoryStore, SetSource};
+    use crate::types::{EnergyType, Rarity, Subtype, Supertype};
     use crate::{card::GetCardRequest, client::Client};
+    use futures::{StreamExt, TryStreamExt};
+    use reqwest::header::{HeaderMap, HeaderValue};
+    use serde_json::Map;
+    use std::ops::Bound;
+    use std::time::{Duration, Instant};
     use wiremock::matchers::{header, path, query_param};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
@@ -46,6 +86,238 @@ mod tests {
         let _result = client.get_card(GetCardRequest::new(test_card_id)).await;
     }
 
+    #[tokio::test]
+    async fn builder_applies_headers_to_every_request() {
+        let mock_server = MockServer::start().await;
+        let mut default_headers = HeaderMap::new();
+        default_headers.insert("X-Trace", HeaderValue::from_static("trace-1"));
+        let client = Client::builder()
+            .base_url(mock_server.uri().as_str())
+            .api_key("abc123")
+            .user_agent("sdk-test/1.0")
+            .default_headers(default_headers)
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap();
+
+        Mock::given(path("/types"))
+            .and(header("X-Api-Key", "abc123"))
+            .and(header("User-Agent", "sdk-test/1.0"))
+            .and(header("X-Trace", "trace-1"))
+            .respond_with(ResponseTemplate::new(400))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let _result = client.get_types().await;
+    }
+
+    #[tokio::test]
+    async fn builder_uses_custom_http_client() {
+        let mock_server = MockServer::start().await;
+        let client = Client::builder()
+            .base_url(mock_server.uri().as_str())
+            .api_key("abc123")
+            .http_client(reqwest::Client::new())
+            .build()
+            .unwrap();
+
+        Mock::given(path("/types"))
+            .and(header("X-Api-Key", "abc123"))
+            .respond_with(ResponseTemplate::new(400))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let _result = client.get_types().await;
+    }
+
+    #[test]
+    fn builder_rejects_invalid_user_agent() {
+        let result = Client::builder().user_agent("bad\nagent").build();
+
+        assert!(matches!(result, Err(ClientError::InvalidUserAgent(_))));
+    }
+
+    fn fast_retry_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(1),
+            jitter: false,
+            ..RetryPolicy::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_transient_errors_until_success() {
+        let mock_server = MockServer::start().await;
+        let client = Client::builder()
+            .base_url(mock_server.uri().as_str())
+            .retry_policy(fast_retry_policy())
+            .build()
+            .unwrap();
+        let body = ApiResult::Ok(DataEnvelope {
+            data: vec![String::from("Fire")],
+            page: None,
+            page_size: None,
+            count: None,
+            total_count: None,
+        });
+
+        Mock::given(path("/types"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(path("/types"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let result = client.get_types().await.unwrap();
+        assert_eq!(result, vec![EnergyType::Fire]);
+    }
+
+    #[tokio::test]
+    async fn stops_retrying_after_max_attempts() {
+        let mock_server = MockServer::start().await;
+        let client = Client::builder()
+            .base_url(mock_server.uri().as_str())
+            .retry_policy(fast_retry_policy())
+            .build()
+            .unwrap();
+
+        Mock::given(path("/types"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+            .expect(3)
+            .mount(&mock_server)
+            .await;
+
+        let result = client.get_types().await;
+        assert!(matches!(result, Err(ClientError::TooManyRequests(_))));
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_client_errors() {
+        let mock_server = MockServer::start().await;
+        let client = Client::builder()
+            .base_url(mock_server.uri().as_str())
+            .retry_policy(fast_retry_policy())
+            .build()
+            .unwrap();
+
+        Mock::given(path("/types"))
+            .respond_with(ResponseTemplate::new(400))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let result = client.get_types().await;
+        assert!(matches!(result, Err(ClientError::BadRequest(_))));
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_maximum() {
+        let policy = RetryPolicy {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(5),
+            jitter: false,
+            ..RetryPolicy::default()
+        };
+
+        assert_eq!(policy.backoff(1, None), Duration::from_secs(1));
+        assert_eq!(policy.backoff(3, None), Duration::from_secs(4));
+        assert_eq!(policy.backoff(4, None), Duration::from_secs(5));
+        assert_eq!(
+            policy.backoff(1, Some(Duration::from_secs(9))),
+            Duration::from_secs(9)
+        );
+    }
+
+    #[test]
+    fn retry_after_is_capped() {
+        let policy = RetryPolicy {
+            max_retry_after: Duration::from_secs(60),
+            jitter: false,
+            ..RetryPolicy::default()
+        };
+
+        assert_eq!(
+            policy.backoff(1, Some(Duration::from_secs(86400))),
+            Duration::from_secs(60)
+        );
+        assert_eq!(
+            policy.backoff(1, Some(Duration::from_secs(2))),
+            Duration::from_secs(2)
+        );
+    }
+
+    #[tokio::test]
+    async fn rate_limit_is_shared_between_clones() {
+        let mock_server = MockServer::start().await;
+        let client = Client::builder()
+            .base_url(mock_server.uri().as_str())
+            .rate_limit(RateLimit::per_second(20).burst(1))
+            .build()
+            .unwrap();
+        let clone = client.clone();
+
+        Mock::given(path("/types"))
+            .respond_with(ResponseTemplate::new(400))
+            .expect(4)
+            .mount(&mock_server)
+            .await;
+
+        let start = Instant::now();
+        let _result = tokio::join!(
+            client.get_types(),
+            clone.get_types(),
+            client.get_types(),
+            clone.get_types()
+        );
+
+        // the first request passes immediately, the three others wait 50ms each
+        assert!(start.elapsed() >= Duration::from_millis(150));
+    }
+
+    #[tokio::test]
+    async fn cancelled_rate_limit_waits_give_their_token_back() {
+        let limiter = crate::rate_limit::RateLimiter::new(RateLimit::per_second(10).burst(1));
+        assert_eq!(limiter.reserve(), Duration::ZERO);
+
+        let cancelled = tokio::time::timeout(Duration::from_millis(10), limiter.acquire()).await;
+        assert!(cancelled.is_err());
+
+        // only the first request is still ahead, so the next one waits at most one interval
+        assert!(limiter.reserve() <= Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn get_card_as_returns_partial_response() {
+        let mock_server = MockServer::start().await;
+        let client = Client::with_base_url(mock_server.uri().as_str(), None).unwrap();
+        let body = serde_json::json!({ "data": { "id": "base1-5", "name": "Clefable" } });
+
+        Mock::given(path("/cards/base1-5"))
+            .and(query_param("select", "id,name"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let card: serde_json::Value = client
+            .get_card_as(GetCardRequest {
+                select: Some(vec![String::from("id"), String::from("name")]),
+                ..GetCardRequest::new("base1-5")
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(card["name"], "Clefable");
+    }
+
     #[tokio::test]
     async fn search_cards_sends_request_with_params() {
         let mock_server = MockServer::start().await;
@@ -54,6 +326,7 @@ mod tests {
         let page = 1;
         let page_size = 250;
         let order_by = "name,-number";
+        let order = OrderBy::asc(CardSort::Name).then_desc(CardSort::Number);
 
         Mock::given(path("/cards"))
             .and(query_param("q", query))
@@ -70,7 +343,29 @@ mod tests {
                 query: Some(String::from(query)),
                 page: Some(page),
                 page_size: Some(page_size),
-                order_by: Some(String::from(order_by)),
+                order_by: Some(order),
+                select: None,
+            })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn search_cards_sends_select_param() {
+        let mock_server = MockServer::start().await;
+        let client = Client::with_base_url(mock_server.uri().as_str(), None).unwrap();
+
+        Mock::given(path("/cards"))
+            .and(query_param("q", "name:celebi"))
+            .and(query_param("select", "id,name"))
+            .respond_with(ResponseTemplate::new(400))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let _result = client
+            .search_cards(SearchCardsRequest {
+                select: Some(vec![String::from("id"), String::from("name")]),
+                ..SearchCardsRequest::new("name:celebi")
             })
             .await;
     }
@@ -81,6 +376,9 @@ mod tests {
         let client = Client::with_base_url(mock_server.uri().as_str(), None).unwrap();
         let body = ApiResult::Ok(DataEnvelope {
             data: vec![Card::default()],
+            page: None,
+            page_size: None,
+            count: None,
             // greater than the single page size
             total_count: Some(251),
         });
@@ -96,6 +394,869 @@ mod tests {
         let _result = client.get_all_cards().await;
     }
 
+    #[tokio::test]
+    async fn stream_cards_fetches_pages_until_total_count() {
+        let mock_server = MockServer::start().await;
+        let client = Client::with_base_url(mock_server.uri().as_str(), None).unwrap();
+        let body = ApiResult::Ok(DataEnvelope {
+            data: vec![Card::default(), Card::default()],
+            page: None,
+            page_size: None,
+            count: None,
+            total_count: Some(4),
+        });
+
+        Mock::given(path("/cards"))
+            .and(query_param("q", "set.id:base1"))
+            .and(query_param("pageSize", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let cards: Vec<Card> = client
+            .stream_cards(SearchCardsRequest {
+                page_size: Some(2),
+                ..SearchCardsRequest::new("set.id:base1")
+            })
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(cards.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn stream_cards_stops_fetching_when_dropped() {
+        let mock_server = MockServer::start().await;
+        let client = Client::with_base_url(mock_server.uri().as_str(), None).unwrap();
+        let body = ApiResult::Ok(DataEnvelope {
+            data: vec![Card::default(), Card::default()],
+            page: None,
+            page_size: None,
+            count: None,
+            total_count: Some(1000),
+        });
+
+        Mock::given(path("/cards"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let cards: Vec<_> = client
+            .stream_cards(SearchCardsRequest {
+                page_size: Some(2),
+                ..SearchCardsRequest::default()
+            })
+            .take(2)
+            .collect()
+            .await;
+
+        assert_eq!(cards.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn search_cards_returns_page_metadata() {
+        let mock_server = MockServer::start().await;
+        let client = Client::with_base_url(mock_server.uri().as_str(), None).unwrap();
+        let body = ApiResult::Ok(DataEnvelope {
+            data: vec![Card::default(), Card::default()],
+            page: Some(2),
+            page_size: Some(2),
+            count: Some(2),
+            total_count: Some(5),
+        });
+
+        Mock::given(path("/cards"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let request = SearchCardsRequest {
+            page: Some(2),
+            page_size: Some(2),
+            ..SearchCardsRequest::new("name:celebi")
+        };
+        let page = client.search_cards(request.clone()).await.unwrap();
+
+        assert_eq!(page.count, 2);
+        assert_eq!(page.total_count, 5);
+        assert_eq!(page.total_pages(), 3);
+        assert!(page.has_next());
+
+        let next = page.next_request(&request).unwrap();
+        assert_eq!(next.page, Some(3));
+        assert_eq!(next.page_size, Some(2));
+        assert_eq!(next.query, request.query);
+    }
+
+    #[test]
+    fn last_page_has_no_next_request() {
+        let page = Page {
+            data: vec![Card::default()],
+            page: 3,
+            page_size: 2,
+            count: 1,
+            total_count: 5,
+        };
+
+        assert!(!page.has_next());
+        assert!(page
+            .next_request(&SearchCardsRequest::new("name:celebi"))
+            .is_none());
+    }
+
+    #[test]
+    fn card_query_escapes_values() {
+        let query = CardQuery::new()
+            .name(Match::exact("Farfetch'd"))
+            .attack_name(r#"Say "Hi""#)
+            .evolves_from(Match::wildcard("Mr. M*(Galar)"))
+            .number("TG-12");
+
+        assert_eq!(
+            query.to_string(),
+            r#"!name:Farfetch'd attacks.name:"Say \"Hi\"" evolvesFrom:Mr.\ M*\(Galar\) number:"TG-12""#
+        );
+    }
+
+    #[test]
+    fn card_query_renders_ranges() {
+        let query = CardQuery::new()
+            .hp(Match::range(60..=120))
+            .national_pokedex_number(Match::range(..151))
+            .field(
+                CardField::ConvertedRetreatCost,
+                Match::range((Bound::Excluded(1), Bound::Unbounded)),
+            );
+
+        assert_eq!(
+            query.to_string(),
+            "hp:[60 TO 120] nationalPokedexNumbers:[* TO 151} convertedRetreatCost:{1 TO *]"
+        );
+    }
+
+    #[test]
+    fn empty_queries_are_ignored() {
+        let fire = || CardQuery::new().types("Fire");
+
+        assert_eq!(CardQuery::new().not(CardQuery::new()).to_string(), "");
+        assert_eq!(CardQuery::new().or(fire()).to_string(), "types:Fire");
+        assert_eq!(fire().or(CardQuery::new()).to_string(), "types:Fire");
+        assert_eq!(fire().not(CardQuery::new()).to_string(), fire().to_string());
+        assert_eq!(SearchCardsRequest::from(CardQuery::new()).query, None);
+        assert_eq!(
+            SearchCardsRequest::from(fire()).query.as_deref(),
+            Some("types:Fire")
+        );
+
+        let base = || SetQuery::new().series("Base");
+        assert_eq!(SetQuery::new().not(SetQuery::new()).to_string(), "");
+        assert_eq!(SetQuery::new().or(base()).to_string(), "series:Base");
+        assert_eq!(SearchSetsRequest::from(SetQuery::new()).query, None);
+    }
+
+    #[test]
+    fn card_query_groups_nested_conditions() {
+        let query = CardQuery::new()
+            .set(SetField::Series, "Base")
+            .and(
+                CardQuery::new()
+                    .types("Fire")
+                    .or(CardQuery::new().types("Water"))
+                    .or(CardQuery::new().types("Grass")),
+            )
+            .not(CardQuery::new().supertype("Trainer").rarity("Common"));
+
+        assert_eq!(
+            query.to_string(),
+            "set.series:Base (types:Fire OR types:Water OR types:Grass) -(supertype:Trainer rarity:Common)"
+        );
+    }
+
+    #[tokio::test]
+    async fn search_cards_accepts_card_query() {
+        let mock_server = MockServer::start().await;
+        let client = Client::with_base_url(mock_server.uri().as_str(), None).unwrap();
+
+        Mock::given(path("/cards"))
+            .and(query_param("q", "set.id:sv4 -subtypes:ex"))
+            .respond_with(ResponseTemplate::new(400))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let query = CardQuery::new()
+            .set_id("sv4")
+            .not(CardQuery::new().subtype("ex"));
+        let _result = client.search_cards(query.into()).await;
+    }
+
+    #[test]
+    fn query_parser_round_trips_rendered_queries() {
+        for query in [
+            "name:char* hp:[100 TO *] -subtypes:Mega",
+            r#"!name:"Venusaur V" attacks.name:"Fire Blast""#,
+            "set.series:Base (types:Fire OR types:Water) -(supertype:Trainer rarity:Common)",
+            r#"set.releaseDate:{"2020/01/01" TO *] nationalPokedexNumbers:[1 TO 151}"#,
+            "hp:[* TO *]",
+        ] {
+            assert_eq!(Query::parse(query).unwrap().to_string(), query);
+        }
+
+        assert_eq!(
+            Query::parse("types:fire AND NOT subtypes:ex || types:water").unwrap(),
+            Query::parse("(types:fire -subtypes:ex) OR types:water").unwrap()
+        );
+    }
+
+    #[test]
+    fn query_parser_rejects_invalid_syntax() {
+        for query in [
+            "charizard",
+            "name:",
+            "(name:charizard",
+            "name:charizard)",
+            "hp:[100 TO",
+            r#"name:"venusaur"#,
+            "name:charizard OR",
+        ] {
+            assert!(Query::parse(query).is_err(), "{} should be invalid", query);
+        }
+
+        let error = SearchCardsRequest::new("types:fire hp:100]")
+            .validate()
+            .unwrap_err();
+        assert_eq!(error.position, 17);
+    }
+
+    #[test]
+    fn card_enums_fall_back_to_unknown_values() {
+        let card: Card = serde_json::from_value(serde_json::json!({
+            "id": "swsh4-44", "name": "Pikachu VMAX", "supertype": "Pokémon",
+            "subtypes": ["VMAX", "Gigantamax"], "types": ["Lightning"], "rarity": "Rare Holo EX",
+            "attacks": [{ "cost": ["Lightning", "Aether"], "name": "G-Max Volt Tackle",
+                "text": "", "damage": "120+", "convertedEnergyCost": 2 }],
+            "weaknesses": [{ "type": "Fighting", "value": "×2" }],
+            "retreatCost": ["Colorless", "Colorless"],
+            "set": Set::default()
+        }))
+        .unwrap();
+
+        assert_eq!(card.supertype, Supertype::Pokemon);
+        assert_eq!(
+            card.subtypes.as_deref(),
+            Some(&[Subtype::VMax, Subtype::Unknown(String::from("Gigantamax"))][..])
+        );
+        assert_eq!(card.rarity, Some(Rarity::RareHoloEx));
+        assert_eq!(
+            card.attacks.as_ref().unwrap()[0].cost,
+            vec![EnergyType::Lightning, EnergyType::from("Aether")]
+        );
+        assert_eq!(
+            card.weaknesses.as_ref().unwrap()[0].type_name,
+            EnergyType::Fighting
+        );
+
+        let value = serde_json::to_value(&card).unwrap();
+        assert_eq!(value["supertype"], "Pokémon");
+        assert_eq!(value["subtypes"], serde_json::json!(["VMAX", "Gigantamax"]));
+        assert_eq!(value["attacks"][0]["cost"][1], "Aether");
+        assert_eq!(
+            CardQuery::new().subtype(Subtype::Stage1).to_string(),
+            r#"subtypes:"Stage 1""#
+        );
+        assert_eq!(Subtype::from("Fusion Strike"), Subtype::FusionStrike);
+    }
+
+    #[test]
+    fn tcgplayer_prices_are_keyed_by_print_variant() {
+        let tcgplayer: TcgPlayer = serde_json::from_value(serde_json::json!({
+            "url": "https://prices.pokemontcg.io/tcgplayer/swsh4-44",
+            "updatedAt": "2021/08/04",
+            "prices": {
+                "holofoil": { "low": 1.5, "mid": 2.5, "high": 9.99, "market": 2.25 },
+                "reverseHolofoil": { "low": 3.0, "market": 4.5, "directLow": 3.75 }
+            }
+        }))
+        .unwrap();
+
+        let prices = tcgplayer.prices.unwrap();
+        assert_eq!(prices.market_price(PrintVariant::Holofoil), Some(2.25));
+        assert_eq!(
+            prices.market_price(PrintVariant::ReverseHolofoil),
+            Some(4.5)
+        );
+        assert_eq!(prices.market_price(PrintVariant::Normal), None);
+        assert_eq!(
+            prices
+                .get(&PrintVariant::ReverseHolofoil)
+                .unwrap()
+                .direct_low,
+            Some(3.75)
+        );
+        assert_eq!(
+            prices.variants().collect::<Vec<_>>(),
+            vec![&PrintVariant::Holofoil, &PrintVariant::ReverseHolofoil]
+        );
+
+        let value = serde_json::to_value(&prices).unwrap();
+        assert_eq!(value["reverseHolofoil"]["market"], 4.5);
+    }
+
+    #[test]
+    fn set_dates_round_trip_in_the_api_format() {
+        let set: Set = serde_json::from_value(serde_json::json!({
+            "id": "base1", "name": "Base", "series": "Base", "printedTotal": 102, "total": 102,
+            "legalities": { "unlimited": "Legal" }, "releaseDate": "1999/01/09",
+            "updatedAt": "2020/08/14 09:35:00", "images": { "symbol": "s.png", "logo": "l.png" }
+        }))
+        .unwrap();
+
+        assert_eq!(set.release_date, "1999/01/09");
+        assert_eq!(set.updated_at, "2020/08/14 09:35:00");
+
+        let value = serde_json::to_value(&set).unwrap();
+        assert_eq!(value["release_date"], "1999/01/09");
+        assert_eq!(value["updated_at"], "2020/08/14 09:35:00");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn malformed_dates_fail_to_deserialize() {
+        use chrono::Datelike;
+
+        let tcgplayer: TcgPlayer = serde_json::from_value(serde_json::json!({
+            "url": "https://prices.pokemontcg.io/tcgplayer/base1-4", "updatedAt": "2021/08/04"
+        }))
+        .unwrap();
+        assert_eq!(tcgplayer.updated_at.as_deref(), Some("2021/08/04"));
+        assert_eq!(tcgplayer.updated_at().unwrap().unwrap().month(), 8);
+
+        let error = serde_json::from_value::<TcgPlayer>(serde_json::json!({
+            "url": "https://prices.pokemontcg.io/tcgplayer/base1-4", "updatedAt": "08/04/2021"
+        }))
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid date `08/04/2021`, expected the format YYYY/MM/DD"
+        );
+        assert!(crate::date::parse_date_time("2020/08/14").is_err());
+
+        let set = Set {
+            release_date: String::from("1999/01/09"),
+            updated_at: String::from("2020/08/14 09:35:00"),
+            ..Set::default()
+        };
+        assert_eq!(set.release_date().unwrap().year(), 1999);
+        assert_eq!(set.updated_at().unwrap().date().day(), 14);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[tokio::test]
+    async fn get_set_reports_malformed_dates() {
+        use std::error::Error;
+
+        let mock_server = MockServer::start().await;
+        let client = Client::with_base_url(mock_server.uri().as_str(), None).unwrap();
+
+        Mock::given(path("/sets/base1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "id": "base1", "name": "Base", "series": "Base", "printedTotal": 102,
+                    "total": 102, "legalities": { "unlimited": "Legal" },
+                    "releaseDate": "01/09/1999", "updatedAt": "2020/08/14 09:35:00",
+                    "images": { "symbol": "s.png", "logo": "l.png" }
+                }
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let error = client
+            .get_set(GetSetRequest::new("base1"))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, ClientError::DecodeFailed(_)));
+        let message = error.source().unwrap().to_string();
+        assert!(
+            message.contains("Invalid date `01/09/1999`, expected the format YYYY/MM/DD"),
+            "{}",
+            message
+        );
+    }
+
+    #[tokio::test]
+    async fn decode_errors_name_the_failing_field() {
+        let mock_server = MockServer::start().await;
+        let client = Client::with_base_url(mock_server.uri().as_str(), None).unwrap();
+
+        Mock::given(path("/cards/base1-4"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "id": "base1-4", "name": "Charizard", "supertype": "Pokémon",
+                    "attacks": [{ "cost": ["Fire"], "name": "Fire Spin", "text": "",
+                        "damage": "100", "convertedEnergyCost": "four" }],
+                    "set": Set::default()
+                }
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let error = client
+            .get_card(GetCardRequest::new("base1-4"))
+            .await
+            .unwrap_err();
+
+        match &error {
+            ClientError::DecodeFailed(e) => {
+                assert_eq!(e.path().to_string(), "data.attacks[0].convertedEnergyCost")
+            }
+            other => panic!("expected a decode error, got {:?}", other),
+        }
+        assert!(error
+            .to_string()
+            .contains("data.attacks[0].convertedEnergyCost"));
+    }
+
+    #[test]
+    fn parses_hp_damage_and_weakness_values() {
+        let attack = |damage: &str| Attack {
+            damage: String::from(damage),
+            ..local_card("base1-4", "Charizard", "120", &[], &[])
+                .attacks
+                .unwrap()[0]
+                .clone()
+        };
+        let damage = |base, modifier| Some(Damage { base, modifier });
+
+        assert_eq!(
+            local_card("base1-4", "Charizard", "120", &[], &[]).hp(),
+            Some(120)
+        );
+        assert_eq!(Card::default().hp(), None);
+        assert_eq!(attack("100").damage(), damage(100, DamageModifier::None));
+        assert_eq!(attack("30+").damage(), damage(30, DamageModifier::Plus));
+        assert_eq!(attack("20×").damage(), damage(20, DamageModifier::Times));
+        assert_eq!(attack("120-").damage(), damage(120, DamageModifier::Minus));
+        assert_eq!(attack("").damage(), None);
+        assert_eq!(attack("20×").damage().unwrap().to_string(), "20×");
+
+        let weakness = |value: &str| Weakness {
+            type_name: EnergyType::Water,
+            value: String::from(value),
+            extra: Map::new(),
+        };
+        assert_eq!(
+            weakness("×2").modifier(),
+            Some(WeaknessModifier::Multiply(2))
+        );
+        assert_eq!(weakness("+20").modifier(), Some(WeaknessModifier::Add(20)));
+        assert_eq!(WeaknessModifier::Multiply(2).apply(60), 120);
+
+        let resistance = Resistance {
+            type_name: EnergyType::Fighting,
+            value: String::from("-30"),
+            extra: Map::new(),
+        };
+        assert_eq!(resistance.modifier(), Some(WeaknessModifier::Add(-30)));
+        assert_eq!(resistance.modifier().unwrap().apply(20), 0);
+        assert_eq!(resistance.modifier().unwrap().to_string(), "-30");
+    }
+
+    #[test]
+    fn card_ids_split_into_set_and_collector_number() {
+        let id: CardId = "sv3pt5-151".parse().unwrap();
+        assert_eq!(id, CardId::new("sv3pt5", "151"));
+        assert_eq!(id.number.number(), Some(151));
+        assert_eq!(id.to_string(), "sv3pt5-151");
+        assert!("base1".parse::<CardId>().is_err());
+
+        let number = CollectorNumber::from("SWSH001");
+        assert_eq!(
+            (number.prefix(), number.number(), number.suffix()),
+            ("SWSH", Some(1), "")
+        );
+        assert_eq!(CollectorNumber::from("25a").suffix(), "a");
+
+        let mut numbers: Vec<CollectorNumber> = ["100", "TG12", "10", "2", "25a", "1", "25"]
+            .into_iter()
+            .map(CollectorNumber::from)
+            .collect();
+        numbers.sort();
+        let numbers: Vec<&str> = numbers.iter().map(CollectorNumber::as_str).collect();
+        assert_eq!(numbers, vec!["1", "2", "10", "25", "25a", "100", "TG12"]);
+
+        let set = Set {
+            printed_total: 102,
+            ..Set::default()
+        };
+        assert!(!CollectorNumber::from("102").is_secret_rare(&set));
+        assert!(CollectorNumber::from("103").is_secret_rare(&set));
+        assert!(!CollectorNumber::from("TG12").is_secret_rare(&set));
+        assert!(CollectorNumber::from("TG103").is_secret_rare(&set));
+    }
+
+    #[test]
+    fn legalities_report_legal_and_banned_formats() {
+        let card: Card = serde_json::from_value(serde_json::json!({
+            "id": "xy7-54", "name": "Lysandre's Trump Card", "supertype": "Trainer",
+            "legalities": { "unlimited": "Legal", "expanded": "Banned" },
+            "set": Set::default()
+        }))
+        .unwrap();
+
+        let legalities = card.legalities.unwrap();
+        assert_eq!(
+            legalities.status(Format::Expanded),
+            Some(&LegalityStatus::Banned)
+        );
+        assert_eq!(legalities.status(Format::Standard), None);
+        assert_eq!(legalities.legal_formats(), vec![Format::Unlimited]);
+        assert_eq!(legalities.banned_formats(), vec![Format::Expanded]);
+        assert!(!legalities.is_legal_in(Format::Expanded));
+
+        let value = serde_json::to_value(&legalities).unwrap();
+        assert_eq!(value["expanded"], "Banned");
+        assert!(value["standard"].is_null());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn legality_modules_are_kept_for_compatibility() {
+        let card: crate::card::legality::Legality = Legalities::default();
+        let set: crate::set::legality::Legality = card.clone();
+        assert_eq!(set.status(crate::set::legality::Format::Standard), None);
+    }
+
+    #[test]
+    fn unknown_fields_are_kept_in_extra() {
+        let card: Card = serde_json::from_value(serde_json::json!({
+            "id": "sv1-1", "name": "Pineco", "supertype": "Pokémon", "artistId": 42,
+            "attacks": [{ "cost": [], "name": "Tackle", "text": "", "damage": "10",
+                "effects": ["none"] }],
+            "tcgplayer": { "url": "https://prices.pokemontcg.io/tcgplayer/sv1-1",
+                "prices": { "normal": { "market": 0.1, "marketTrend": "up" } } },
+            "set": Set::default()
+        }))
+        .unwrap();
+
+        assert_eq!(card.extra["artistId"], 42);
+        assert_eq!(
+            card.unknown_fields(),
+            vec![
+                "artistId",
+                "attacks[0].effects",
+                "tcgplayer.prices.normal.marketTrend"
+            ]
+        );
+
+        let value = serde_json::to_value(&card).unwrap();
+        assert_eq!(value["artistId"], 42);
+        assert_eq!(value["attacks"][0]["effects"][0], "none");
+        assert!(Card::default().unknown_fields().is_empty());
+    }
+
+    #[tokio::test]
+    async fn strict_clients_reject_unknown_fields() {
+        let mock_server = MockServer::start().await;
+        let mut set = serde_json::to_value(Set::default()).unwrap();
+        set["languages"] = serde_json::json!(["en"]);
+
+        Mock::given(path("/sets/base1"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "data": set })),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let lenient = Client::with_base_url(mock_server.uri().as_str(), None).unwrap();
+        let set = lenient.get_set(GetSetRequest::new("base1")).await.unwrap();
+        assert_eq!(set.unknown_fields(), vec!["languages"]);
+
+        let strict = Client::builder()
+            .base_url(mock_server.uri().as_str())
+            .strict(true)
+            .build()
+            .unwrap();
+        let error = strict
+            .get_set(GetSetRequest::new("base1"))
+            .await
+            .unwrap_err();
+        assert!(matches!(&error, ClientError::UnknownFields(fields) if fields == &["languages"]));
+        assert!(strict
+            .get_set_as::<serde_json::Value>(GetSetRequest::new("base1"))
+            .await
+            .is_ok());
+
+        // A response cached by an `_as` method is still checked when a model is read from it
+        let cached = Client::builder()
+            .base_url(mock_server.uri().as_str())
+            .cache(CacheConfig::new(Duration::from_secs(60)))
+            .strict(true)
+            .build()
+            .unwrap();
+        assert!(cached
+            .get_set_as::<serde_json::Value>(GetSetRequest::new("base1"))
+            .await
+            .is_ok());
+        let error = cached
+            .get_set(GetSetRequest::new("base1"))
+            .await
+            .unwrap_err();
+        assert!(matches!(&error, ClientError::UnknownFields(fields) if fields == &["languages"]));
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 4);
+    }
+
+    fn local_card(id: &str, name: &str, hp: &str, types: &[&str], cost: &[&str]) -> Card {
+        let to_types = |values: &[&str]| values.iter().map(|v| EnergyType::from(*v)).collect();
+        Card {
+            id: String::from(id),
+            name: String::from(name),
+            supertype: Supertype::Pokemon,
+            hp: Some(String::from(hp)),
+            types: Some(to_types(types)),
+            attacks: Some(vec![Attack {
+                cost: to_types(cost),
+                name: String::from("Tackle"),
+                text: String::new(),
+                damage: String::from("10"),
+                converted_energy_cost: Some(cost.len()),
+                extra: Map::new(),
+            }]),
+            ..Card::default()
+        }
+    }
+
+    fn local_cards() -> Vec<Card> {
+        vec![
+            local_card("base1-4", "Charizard", "120", &["Fire"], &["Fire", "Fire"]),
+            local_card("base1-2", "Blastoise", "100", &["Water"], &["Water"]),
+            local_card("swsh3-20", "Charizard VMAX", "330", &["Fire"], &["Fire"]),
+            local_card("base1-58", "Pikachu", "40", &["Lightning"], &["Colorless"]),
+        ]
+    }
+
+    #[test]
+    fn query_evaluator_matches_like_the_api() {
+        let cards = local_cards();
+        let ids = |query: &str| -> Vec<String> {
+            Query::parse(query)
+                .unwrap()
+                .filter(&cards)
+                .into_iter()
+                .map(|card| card.id)
+                .collect()
+        };
+
+        assert_eq!(ids("name:charizard"), vec!["base1-4", "swsh3-20"]);
+        assert_eq!(ids("!name:charizard"), vec!["base1-4"]);
+        assert_eq!(ids(r#"name:"charizard vmax""#), vec!["swsh3-20"]);
+        assert_eq!(ids("name:*stoise"), vec!["base1-2"]);
+        assert_eq!(ids("hp:[100 TO 120]"), vec!["base1-4", "base1-2"]);
+        assert_eq!(ids("hp:{100 TO *] -types:fire"), Vec::<String>::new());
+        assert_eq!(ids("hp:[* TO *]").len(), 4);
+        assert_eq!(
+            ids("attacks.cost:water OR types:lightning"),
+            vec!["base1-2", "base1-58"]
+        );
+        assert_eq!(ids("attacks.convertedEnergyCost:2"), vec!["base1-4"]);
+        assert_eq!(ids("supertype:pokemon -name:charizard").len(), 2);
+    }
+
+    #[test]
+    fn search_request_evaluates_order_and_paging_locally() {
+        let request = SearchCardsRequest {
+            page: Some(2),
+            page_size: Some(1),
+            order_by: Some(OrderBy::desc(CardSort::Hp).then_asc(CardSort::Name)),
+            ..SearchCardsRequest::new("supertype:pokemon")
+        };
+
+        let page = request.evaluate(&local_cards()).unwrap();
+
+        assert_eq!(page.total_count, 4);
+        assert_eq!(page.count, 1);
+        assert_eq!(page.data[0].id, "base1-4");
+        assert!(page.has_next());
+    }
+
+    #[test]
+    fn order_by_compares_each_field_one_way() {
+        let mut cards: Vec<Card> = ["10", "TG01", "9", "1a", "100", "2"]
+            .into_iter()
+            .map(|number| Card {
+                number: Some(String::from(number)),
+                ..Card::default()
+            })
+            .collect();
+
+        OrderBy::asc(CardSort::Number).sort(&mut cards);
+
+        let numbers: Vec<&str> = cards
+            .iter()
+            .filter_map(|card| card.number.as_deref())
+            .collect();
+        assert_eq!(numbers, vec!["1a", "2", "9", "10", "100", "TG01"]);
+
+        let mut cards = local_cards();
+        cards[3].hp = Some(String::from("N/A"));
+        OrderBy::asc(CardSort::Hp).sort(&mut cards);
+
+        let ids: Vec<&str> = cards.iter().map(|card| card.id.as_str()).collect();
+        assert_eq!(ids, vec!["base1-2", "base1-4", "swsh3-20", "base1-58"]);
+    }
+
+    #[tokio::test]
+    async fn get_set_cards_orders_by_collector_number() {
+        let mock_server = MockServer::start().await;
+        let client = Client::with_base_url(mock_server.uri().as_str(), None).unwrap();
+        let set = Set {
+            id: String::from("sv4"),
+            printed_total: 182,
+            ..Set::default()
+        };
+        let cards: Vec<Card> = ["TG03", "183", "10", "2", "25a", "25", "TG01", "182"]
+            .iter()
+            .map(|number| Card {
+                number: Some(String::from(*number)),
+                set: set.clone(),
+                ..Card::default()
+            })
+            .collect();
+        let body = ApiResult::Ok(DataEnvelope {
+            data: cards,
+            page: None,
+            page_size: None,
+            count: None,
+            total_count: Some(8),
+        });
+
+        Mock::given(path("/cards"))
+            .and(query_param("q", "set.id:sv4"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let numbers: Vec<String> = set
+            .cards(&client)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter_map(|card| card.number)
+            .collect();
+
+        assert_eq!(
+            numbers,
+            vec!["2", "10", "25", "25a", "182", "183", "TG01", "TG03"]
+        );
+    }
+
+    #[test]
+    fn promo_set_cards_are_ordered_by_their_prefixed_numbers() {
+        let set = Set {
+            id: String::from("swshp"),
+            printed_total: 304,
+            ..Set::default()
+        };
+        let mut cards: Vec<Card> = ["SWSH100", "SWSH020", "SWSH002", "SWSH010"]
+            .iter()
+            .map(|number| Card {
+                number: Some(String::from(*number)),
+                set: set.clone(),
+                ..Card::default()
+            })
+            .collect();
+
+        crate::card::sort_by_collector_number(&mut cards);
+
+        let numbers: Vec<&str> = cards.iter().filter_map(|c| c.number.as_deref()).collect();
+        assert_eq!(numbers, vec!["SWSH002", "SWSH010", "SWSH020", "SWSH100"]);
+        assert!(!CollectorNumber::from("SWSH100").is_secret_rare(&set));
+        assert!(CollectorNumber::from("SWSH305").is_secret_rare(&set));
+    }
+
+    fn card_page(id: &str, total_count: usize) -> ResponseTemplate {
+        let body = ApiResult::Ok(DataEnvelope {
+            data: vec![Card {
+                id: String::from(id),
+                ..Card::default()
+            }],
+            page: None,
+            page_size: Some(1),
+            count: Some(1),
+            total_count: Some(total_count),
+        });
+
+        ResponseTemplate::new(200).set_body_json(body)
+    }
+
+    #[tokio::test]
+    async fn get_all_cards_matching_keeps_query_and_page_order() {
+        let mock_server = MockServer::start().await;
+        let client = Client::with_base_url(mock_server.uri().as_str(), None).unwrap();
+
+        for page in 1..=4 {
+            Mock::given(path("/cards"))
+                .and(query_param("q", "types:fire"))
+                .and(query_param("orderBy", "-hp"))
+                .and(query_param("pageSize", "1"))
+                .and(query_param("page", page.to_string()))
+                .respond_with(
+                    // later pages answer faster, their results must still come last
+                    card_page(&format!("card-{}", page), 4)
+                        .set_delay(Duration::from_millis(100 / page)),
+                )
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+        }
+
+        let cards = client
+            .get_all_cards_matching(SearchCardsRequest {
+                page_size: Some(1),
+                order_by: Some(OrderBy::desc(CardSort::Hp)),
+                ..SearchCardsRequest::new("types:fire")
+            })
+            .await
+            .unwrap();
+        let ids: Vec<String> = cards.into_iter().map(|card| card.id).collect();
+
+        assert_eq!(ids, vec!["card-1", "card-2", "card-3", "card-4"]);
+    }
+
+    #[tokio::test]
+    async fn get_all_cards_matching_fetches_pages_concurrently() {
+        let mock_server = MockServer::start().await;
+        let client = Client::builder()
+            .base_url(mock_server.uri().as_str())
+            .page_concurrency(4)
+            .build()
+            .unwrap();
+
+        Mock::given(path("/cards"))
+            .respond_with(card_page("card", 5).set_delay(Duration::from_millis(200)))
+            .expect(5)
+            .mount(&mock_server)
+            .await;
+
+        let start = Instant::now();
+        let cards = client
+            .get_all_cards_matching(SearchCardsRequest {
+                page_size: Some(1),
+                ..SearchCardsRequest::default()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(cards.len(), 5);
+        // the first page, then the four others at once
+        assert!(start.elapsed() < Duration::from_millis(800));
+    }
+
     #[tokio::test]
     async fn search_sets_sends_request_with_params() {
         let mock_server = MockServer::start().await;
@@ -103,7 +1264,8 @@ mod tests {
         let query = "name:base1";
         let page = 1;
         let page_size = 250;
-        let order_by = "name,-number";
+        let order_by = "name,-releaseDate";
+        let order = OrderBy::asc(SetSort::Name).then_desc(SetSort::ReleaseDate);
 
         Mock::given(path("/sets"))
             .and(query_param("q", query))
@@ -120,7 +1282,29 @@ mod tests {
                 query: Some(String::from(query)),
                 page: Some(page),
                 page_size: Some(page_size),
-                order_by: Some(String::from(order_by)),
+                order_by: Some(order),
+                select: None,
+            })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn search_sets_sends_select_param() {
+        let mock_server = MockServer::start().await;
+        let client = Client::with_base_url(mock_server.uri().as_str(), None).unwrap();
+
+        Mock::given(path("/sets"))
+            .and(query_param("q", "name:base1"))
+            .and(query_param("select", "id,name"))
+            .respond_with(ResponseTemplate::new(400))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let _result = client
+            .search_sets(SearchSetsRequest {
+                select: Some(vec![String::from("id"), String::from("name")]),
+                ..SearchSetsRequest::new("name:base1")
             })
             .await;
     }
@@ -209,4 +1393,508 @@ mod tests {
 
         let _result = client.get_rarities().await;
     }
+
+    fn types_response() -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({ "data": ["Fire", "Water"] }))
+    }
+
+    #[tokio::test]
+    async fn cache_serves_repeated_requests_until_invalidated() {
+        let mock_server = MockServer::start().await;
+        let client = Client::builder()
+            .base_url(mock_server.uri().as_str())
+            .cache(CacheConfig::new(Duration::from_secs(60)))
+            .build()
+            .unwrap();
+
+        Mock::given(path("/types"))
+            .respond_with(types_response())
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        for _ in 0..3 {
+            assert_eq!(
+                client.get_types().await.unwrap(),
+                vec![EnergyType::Fire, EnergyType::Water]
+            );
+        }
+        assert_eq!(client.cache().unwrap().len(), 1);
+
+        client
+            .clone()
+            .cache()
+            .unwrap()
+            .invalidate_endpoint(Endpoint::Types);
+        assert!(client.cache().unwrap().is_empty());
+        client.get_types().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn cache_keys_include_query_parameters() {
+        let mock_server = MockServer::start().await;
+        let client = Client::builder()
+            .base_url(mock_server.uri().as_str())
+            .cache(CacheConfig::new(Duration::from_secs(60)))
+            .build()
+            .unwrap();
+
+        Mock::given(path("/cards"))
+            .respond_with(card_page("base1-4", 1))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        for query in ["name:charizard", "name:pikachu", "name:charizard"] {
+            let request = SearchCardsRequest::new(query);
+            client.search_cards(request).await.unwrap();
+        }
+        assert_eq!(client.cache().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn cache_evicts_least_recently_used_and_expired_entries() {
+        let mock_server = MockServer::start().await;
+        let client = Client::builder()
+            .base_url(mock_server.uri().as_str())
+            .cache(
+                CacheConfig::new(Duration::from_secs(60))
+                    .ttl(Endpoint::Rarities, Duration::from_millis(20))
+                    .ttl(Endpoint::Subtypes, Duration::ZERO)
+                    .max_entries(1),
+            )
+            .build()
+            .unwrap();
+
+        Mock::given(path("/types"))
+            .respond_with(types_response())
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(path("/supertypes"))
+            .respond_with(types_response())
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(path("/subtypes"))
+            .respond_with(types_response())
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(path("/rarities"))
+            .respond_with(types_response())
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        // Fetching supertypes evicts types, which are fetched again
+        client.get_types().await.unwrap();
+        client.get_supertypes().await.unwrap();
+        client.get_types().await.unwrap();
+
+        // A zero TTL disables caching
+        client.get_subtypes().await.unwrap();
+        client.get_subtypes().await.unwrap();
+
+        client.get_rarities().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        client.get_rarities().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn disk_cache_revalidates_stored_responses() {
+        let mock_server = MockServer::start().await;
+        let dir = std::env::temp_dir().join(format!("pokemon-tcg-sdk-{}", fastrand::u64(..)));
+        let build_client = || {
+            Client::builder()
+                .base_url(mock_server.uri().as_str())
+                .disk_cache(DiskCache::new(&dir))
+                .build()
+                .unwrap()
+        };
+
+        Mock::given(path("/types"))
+            .and(header("If-None-Match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(path("/types"))
+            .respond_with(types_response().insert_header("ETag", "\"v1\""))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        assert_eq!(
+            build_client().get_types().await.unwrap(),
+            vec![EnergyType::Fire, EnergyType::Water]
+        );
+
+        // A new client, as after a restart, revalidates the stored response
+        let client = build_client();
+        assert_eq!(
+            client.get_types().await.unwrap(),
+            vec![EnergyType::Fire, EnergyType::Water]
+        );
+
+        let disk_cache = client.disk_cache().unwrap();
+        let entries = disk_cache.entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].url, format!("{}/types", mock_server.uri()));
+        assert_eq!(entries[0].etag.as_deref(), Some("\"v1\""));
+
+        assert_eq!(
+            disk_cache
+                .prune_older_than(Duration::from_secs(60))
+                .unwrap(),
+            0
+        );
+        assert_eq!(disk_cache.prune_to_size(0).unwrap(), 1);
+        assert!(disk_cache.entries().unwrap().is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn sets_response(sets: &[(&str, &str)]) -> ResponseTemplate {
+        let sets: Vec<Set> = sets
+            .iter()
+            .map(|(id, updated_at)| Set {
+                id: String::from(*id),
+                updated_at: String::from(*updated_at),
+                ..Set::default()
+            })
+            .collect();
+        let body = ApiResult::Ok(DataEnvelope {
+            page: None,
+            page_size: Some(250),
+            count: Some(sets.len()),
+            total_count: Some(sets.len()),
+            data: sets,
+        });
+
+        ResponseTemplate::new(200).set_body_json(body)
+    }
+
+    #[tokio::test]
+    async fn mirror_syncs_only_changed_sets() {
+        let mock_server = MockServer::start().await;
+        let client = Client::with_base_url(mock_server.uri().as_str(), None).unwrap();
+        let dir = std::env::temp_dir().join(format!("pokemon-tcg-sdk-{}", fastrand::u64(..)));
+        let mirror = Mirror::new(&dir);
+
+        Mock::given(path("/sets"))
+            .respond_with(sets_response(&[
+                ("base1", "2020/08/14 09:35:00"),
+                ("base2", "2020/08/14 09:35:00"),
+            ]))
+            .up_to_n_times(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(path("/sets"))
+            .respond_with(sets_response(&[("base2", "2024/01/01 00:00:00")]))
+            .mount(&mock_server)
+            .await;
+        Mock::given(path("/cards"))
+            .and(query_param("q", "set.id:base1"))
+            .respond_with(card_page("base1-4", 1))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(path("/cards"))
+            .and(query_param("q", "set.id:base2"))
+            .respond_with(card_page("base2-1", 1))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let report = mirror.sync(&client).await.unwrap();
+        assert_eq!(report.revision, 1);
+        assert_eq!(report.updated_sets, vec!["base1", "base2"]);
+        assert_eq!(mirror.cards("base1").unwrap()[0].id, "base1-4");
+
+        let report = mirror.sync(&client).await.unwrap();
+        assert!(report.is_unchanged());
+        assert_eq!(report.revision, 1);
+
+        let report = mirror.sync(&client).await.unwrap();
+        assert_eq!(report.revision, 2);
+        assert_eq!(report.updated_sets, vec!["base2"]);
+        assert_eq!(report.removed_sets, vec!["base1"]);
+        assert!(mirror.cards("base1").is_err());
+
+        let manifest = mirror.manifest().unwrap().unwrap();
+        assert_eq!(manifest.sets.len(), 1);
+        assert_eq!(manifest.sets["base2"], "2024/01/01 00:00:00");
+        assert_eq!(mirror.all_cards().unwrap().len(), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn interrupted_mirror_sync_keeps_the_current_snapshot() {
+        let mock_server = MockServer::start().await;
+        let client = Client::with_base_url(mock_server.uri().as_str(), None).unwrap();
+        let dir = std::env::temp_dir().join(format!("pokemon-tcg-sdk-{}", fastrand::u64(..)));
+        let mirror = Mirror::new(&dir);
+
+        Mock::given(path("/sets"))
+            .respond_with(sets_response(&[
+                ("base1", "2020/08/14 09:35:00"),
+                ("base2", "2020/08/14 09:35:00"),
+            ]))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(path("/sets"))
+            .respond_with(sets_response(&[
+                ("base1", "2024/01/01 00:00:00"),
+                ("base2", "2024/01/01 00:00:00"),
+            ]))
+            .mount(&mock_server)
+            .await;
+        Mock::given(path("/cards"))
+            .and(query_param("q", "set.id:base1"))
+            .respond_with(card_page("base1-4", 1))
+            .up_to_n_times(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(path("/cards"))
+            .and(query_param("q", "set.id:base1"))
+            .respond_with(card_page("base1-5", 1))
+            .mount(&mock_server)
+            .await;
+        Mock::given(path("/cards"))
+            .and(query_param("q", "set.id:base2"))
+            .respond_with(card_page("base2-1", 1))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(path("/cards"))
+            .and(query_param("q", "set.id:base2"))
+            .respond_with(ResponseTemplate::new(404))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(path("/cards"))
+            .and(query_param("q", "set.id:base2"))
+            .respond_with(card_page("base2-2", 1))
+            .mount(&mock_server)
+            .await;
+
+        assert_eq!(mirror.sync(&client).await.unwrap().revision, 1);
+
+        // base1 is downloaded again before base2 fails
+        assert!(mirror.sync(&client).await.is_err());
+        let current = mirror.current().unwrap().unwrap();
+        assert_eq!(current.revision(), 1);
+        assert_eq!(
+            current.manifest().unwrap().sets["base1"],
+            "2020/08/14 09:35:00"
+        );
+        assert_eq!(mirror.cards("base1").unwrap()[0].id, "base1-4");
+        assert_eq!(mirror.cards("base2").unwrap()[0].id, "base2-1");
+        assert_eq!(mirror.revisions().unwrap(), vec![1]);
+
+        let report = mirror.sync(&client).await.unwrap();
+        assert_eq!(report.revision, 2);
+        assert_eq!(report.updated_sets, vec!["base1", "base2"]);
+        assert_eq!(mirror.cards("base1").unwrap()[0].id, "base1-5");
+
+        // The earlier revision stays readable until it is pruned
+        assert_eq!(mirror.snapshot(1).cards("base1").unwrap()[0].id, "base1-4");
+        assert_eq!(mirror.prune(1).unwrap(), vec![1]);
+        assert_eq!(mirror.revisions().unwrap(), vec![2]);
+        assert!(mirror.snapshot(1).sets().is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn dataset_fills_in_the_set_of_each_card() {
+        let dir = std::env::temp_dir().join(format!("pokemon-tcg-sdk-{}", fastrand::u64(..)));
+        std::fs::create_dir_all(dir.join("sets")).unwrap();
+        std::fs::create_dir_all(dir.join("cards").join("en")).unwrap();
+        std::fs::write(
+            dir.join("sets").join("en.json"),
+            r#"[{
+                "id": "base1", "name": "Base", "series": "Base", "printedTotal": 102, "total": 102,
+                "legalities": { "unlimited": "Legal" }, "ptcgoCode": "BS",
+                "releaseDate": "1999/01/09", "updatedAt": "2020/08/14 09:35:00",
+                "images": { "symbol": "symbol.png", "logo": "logo.png" }
+            }, {
+                "id": "base2", "name": "Jungle", "series": "Base", "printedTotal": 64, "total": 64,
+                "legalities": { "unlimited": "Legal" },
+                "releaseDate": "1999/06/16", "updatedAt": "2020/08/14 09:35:00",
+                "images": { "symbol": "symbol.png", "logo": "logo.png" }
+            }]"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("cards").join("en").join("base1.json"),
+            r#"[{
+                "id": "base1-4", "name": "Charizard", "supertype": "Pokémon",
+                "subtypes": ["Stage 2"], "hp": "120", "evolvesFrom": "Charmeleon",
+                "number": "4", "nationalPokedexNumbers": [6]
+            }]"#,
+        )
+        .unwrap();
+
+        let dataset = Dataset::load(&dir).unwrap();
+        assert_eq!(dataset.sets.len(), 2);
+        assert_eq!(dataset.cards.len(), 1);
+
+        let charizard = dataset.card("base1-4").unwrap();
+        assert_eq!(charizard.set.name, "Base");
+        assert_eq!(charizard.evolves_from.as_deref(), Some("Charmeleon"));
+        assert_eq!(dataset.cards_of("base1").count(), 1);
+        assert_eq!(dataset.cards_of("base2").count(), 0);
+
+        std::fs::write(dir.join("cards").join("en").join("base2.json"), "[{}]").unwrap();
+        let error = Dataset::load(&dir).unwrap_err();
+        assert!(matches!(error, DatasetError::Json { path, .. } if path.ends_with("base2.json")));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// Application code written against the source traits, to run against any of them
+    async fn fire_card_names(source: &impl CardSource) -> Result<Vec<String>, ClientError> {
+        let mut request = SearchCardsRequest::new("types:fire");
+        request.order_by = Some(OrderBy::desc(CardSort::Hp));
+        let page = source.search_cards(request).await?;
+        Ok(page.data.into_iter().map(|card| card.name).collect())
+    }
+
+    #[tokio::test]
+    async fn memory_store_answers_like_the_api() {
+        let store = MemoryStore::new(
+            vec![Set {
+                id: String::from("base1"),
+                ..Set::default()
+            }],
+            local_cards(),
+        );
+
+        assert_eq!(
+            fire_card_names(&store).await.unwrap(),
+            vec!["Charizard VMAX", "Charizard"]
+        );
+        assert_eq!(
+            fire_card_names(&std::sync::Arc::new(store.clone()))
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(
+            store.get_types().await.unwrap(),
+            vec![EnergyType::Fire, EnergyType::Lightning, EnergyType::Water]
+        );
+        assert_eq!(
+            store.get_set(GetSetRequest::new("base1")).await.unwrap().id,
+            "base1"
+        );
+
+        let missing = store.get_card(GetCardRequest::new("base1-999")).await;
+        assert!(matches!(missing, Err(ClientError::NotFound(_))));
+        let invalid = store.search_sets(SearchSetsRequest::new("name:(")).await;
+        assert!(matches!(invalid, Err(ClientError::BadRequest(e)) if e.error.code == 400));
+    }
+
+    #[tokio::test]
+    async fn client_is_a_card_source() {
+        let mock_server = MockServer::start().await;
+        let client = Client::with_base_url(mock_server.uri().as_str(), None).unwrap();
+
+        Mock::given(path("/cards"))
+            .and(query_param("q", "types:fire"))
+            .and(query_param("orderBy", "-hp"))
+            .respond_with(card_page("base1-4", 1))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        assert_eq!(fire_card_names(&client).await.unwrap(), vec![""]);
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn blocking_client_pages_and_retries_without_a_runtime() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let mock_server = runtime.block_on(MockServer::start());
+        let client = crate::blocking::Client::builder()
+            .base_url(mock_server.uri().as_str())
+            .retry_policy(fast_retry_policy())
+            .build()
+            .unwrap();
+
+        runtime.block_on(async {
+            Mock::given(path("/types"))
+                .respond_with(ResponseTemplate::new(503))
+                .up_to_n_times(1)
+                .mount(&mock_server)
+                .await;
+            Mock::given(path("/types"))
+                .respond_with(types_response())
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+            for page in 1..=2 {
+                Mock::given(path("/cards"))
+                    .and(query_param("page", page.to_string()))
+                    .respond_with(card_page(&format!("base1-{}", page), 2))
+                    .expect(1)
+                    .mount(&mock_server)
+                    .await;
+            }
+        });
+
+        assert_eq!(
+            client.get_types().unwrap(),
+            vec![EnergyType::Fire, EnergyType::Water]
+        );
+
+        let mut request = SearchCardsRequest::new("set.id:base1");
+        request.page_size = Some(1);
+        let ids: Vec<String> = client
+            .iter_cards(request)
+            .map(|card| card.unwrap().id)
+            .collect();
+        assert_eq!(ids, vec!["base1-1", "base1-2"]);
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn blocking_get_all_cards_fetches_pages_concurrently_in_order() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let mock_server = runtime.block_on(MockServer::start());
+        let client = crate::blocking::Client::builder()
+            .base_url(mock_server.uri().as_str())
+            .page_concurrency(3)
+            .build()
+            .unwrap();
+
+        runtime.block_on(async {
+            for page in 1..=5u64 {
+                // Earlier pages answer last, so they complete out of order
+                Mock::given(path("/cards"))
+                    .and(query_param("page", page.to_string()))
+                    .respond_with(
+                        card_page(&format!("base1-{}", page), 5)
+                            .set_delay(Duration::from_millis(50 * (5 - page))),
+                    )
+                    .expect(1)
+                    .mount(&mock_server)
+                    .await;
+            }
+        });
+
+        let mut request = SearchCardsRequest::new("set.id:base1");
+        request.page_size = Some(1);
+        let ids: Vec<String> = client
+            .get_all_cards_matching(request)
+            .unwrap()
+            .into_iter()
+            .map(|card| card.id)
+            .collect();
+        assert_eq!(
+            ids,
+            vec!["base1-1", "base1-2", "base1-3", "base1-4", "base1-5"]
+        );
+    }
 }
@@ -0,0 +1,396 @@
+//! A local mirror of the whole catalogue, kept up to date with incremental syncs.
+//!
+//! Every sync that changes the catalogue writes a new snapshot under its own revision directory,
+//! then switches the `current` pointer to it. A snapshot is never modified once written, so
+//! readers always see a single revision, and an interrupted sync leaves the current snapshot as
+//! it was. Sets that didn't change are hard-linked from the previous snapshot rather than copied.
+//!
+//! ```text
+//! current                               the revision of the current snapshot
+//! snapshots/<revision>/manifest.json    format version, revision and the `updated_at` of every set
+//! snapshots/<revision>/sets/en.json     every set
+//! snapshots/<revision>/cards/en/<set>.json  the cards of a set, ordered by collector number
+//! ```
+//!
+//! A snapshot uses the file names of the
+//! [pokemon-tcg-data](https://github.com/PokemonTCG/pokemon-tcg-data) repository. The files hold
+//! the models as this crate serializes them, with snake_case field names such as `release_date`,
+//! rather than the camelCase of the API. Tools written for pokemon-tcg-data can't read them, but
+//! [`Dataset::load`](crate::dataset::Dataset::load) can load a [`Snapshot::dir`].
+//!
+//! ```no_run
+//! # use pokemon_tcg_sdk::{client::Client, mirror::Mirror};
+//! # async fn run() -> Result<(), pokemon_tcg_sdk::errors::MirrorError> {
+//! let client = Client::with_api_key("YOUR_API_KEY")?;
+//! let mirror = Mirror::new("./mirror");
+//!
+//! // The first sync downloads everything, later ones only the sets that changed
+//! let report = mirror.sync(&client).await?;
+//! println!("revision {}: {} sets updated", report.revision, report.updated_sets.len());
+//!
+//! let cards = mirror.cards("base1")?;
+//!
+//! // An earlier revision stays readable until it is pruned
+//! let first = mirror.snapshot(1).sets()?;
+//! mirror.prune(3)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{card::Card, client::Client, errors::MirrorError, set::Set, source::MemoryStore};
+
+/// The version of the mirror layout written by this crate
+pub const FORMAT_VERSION: u32 = 2;
+
+const LANGUAGE: &str = "en";
+
+/// A mirror of every set and card under a directory.
+#[derive(Debug, Clone)]
+pub struct Mirror {
+    dir: PathBuf,
+}
+
+/// The mirror as it was at one revision. Its files are never modified.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    dir: PathBuf,
+    revision: u64,
+}
+
+/// Describes the state of a mirror.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// The version of the layout, see [`FORMAT_VERSION`].
+    pub format_version: u32,
+    /// Incremented by every sync that changed the mirror.
+    pub revision: u64,
+    /// The `updated_at` of every mirrored set when it was last synced, by set id.
+    pub sets: BTreeMap<String, String>,
+}
+
+/// What a sync changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    /// The revision of the mirror after the sync.
+    pub revision: u64,
+    /// The sets whose cards were downloaded, because they were new or updated.
+    pub updated_sets: Vec<String>,
+    /// The sets that are no longer returned by the API and were removed.
+    pub removed_sets: Vec<String>,
+}
+
+impl SyncReport {
+    /// Whether the mirror was already up to date.
+    pub fn is_unchanged(&self) -> bool {
+        self.updated_sets.is_empty() && self.removed_sets.is_empty()
+    }
+}
+
+impl Mirror {
+    /// Mirrors the catalogue under `dir`, which is created by the first sync.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Mirror { dir: dir.into() }
+    }
+
+    /// The directory of the mirror.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The current snapshot, or `None` if the mirror has never been synced.
+    ///
+    /// Reading through the returned snapshot keeps seeing the same revision, even if a sync
+    /// completes in the meantime.
+    pub fn current(&self) -> Result<Option<Snapshot>, MirrorError> {
+        let revision = match fs::read_to_string(self.dir.join("current")) {
+            Ok(revision) => revision,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let revision = revision.trim().parse().map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid current mirror revision",
+            )
+        })?;
+
+        Ok(Some(self.snapshot(revision)))
+    }
+
+    /// The snapshot of a revision. Reading from it fails if the revision was never written or
+    /// was pruned.
+    pub fn snapshot(&self, revision: u64) -> Snapshot {
+        Snapshot {
+            dir: self.snapshot_dir(&revision.to_string()),
+            revision,
+        }
+    }
+
+    /// Lists the revisions that have a snapshot, oldest first.
+    pub fn revisions(&self) -> Result<Vec<u64>, MirrorError> {
+        let entries = match fs::read_dir(self.dir.join("snapshots")) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut revisions = vec![];
+        for entry in entries {
+            if let Some(revision) = entry?.file_name().to_str().and_then(|n| n.parse().ok()) {
+                revisions.push(revision);
+            }
+        }
+
+        revisions.sort_unstable();
+        Ok(revisions)
+    }
+
+    /// Removes every snapshot but the last `keep` revisions. The current snapshot is always kept.
+    /// Returns the removed revisions.
+    pub fn prune(&self, keep: usize) -> Result<Vec<u64>, MirrorError> {
+        let current = self.current()?.map(|snapshot| snapshot.revision);
+        let revisions = self.revisions()?;
+        let removed: Vec<u64> = revisions
+            .iter()
+            .rev()
+            .skip(keep)
+            .filter(|revision| Some(**revision) != current)
+            .copied()
+            .collect();
+
+        for revision in &removed {
+            fs::remove_dir_all(self.snapshot(*revision).dir)?;
+        }
+
+        Ok(removed)
+    }
+
+    /// Reads the manifest of the current snapshot, or `None` if the mirror has never been synced.
+    ///
+    /// # Errors
+    /// Fails if the manifest can't be read or was written by an incompatible version of this crate.
+    pub fn manifest(&self) -> Result<Option<Manifest>, MirrorError> {
+        self.current()?
+            .map(|snapshot| snapshot.manifest())
+            .transpose()
+    }
+
+    /// Brings the mirror up to date. Every set is listed, but cards are only downloaded for sets
+    /// that are new, whose `updated_at` changed since the last sync, or whose cards are missing.
+    ///
+    /// When anything changed, the sets are written to a new snapshot, which becomes current once
+    /// complete. If the sync fails, the current snapshot is left untouched and the next sync starts
+    /// over. The files are written with snake_case field names, see the [module docs](self).
+    pub async fn sync(&self, client: &Client) -> Result<SyncReport, MirrorError> {
+        let current = self.current()?;
+        let mut manifest = match &current {
+            Some(snapshot) => snapshot.manifest()?,
+            None => Manifest {
+                format_version: FORMAT_VERSION,
+                ..Manifest::default()
+            },
+        };
+
+        let mut sets = client.get_all_sets().await?;
+        sets.sort_by(|a, b| (&a.release_date, &a.id).cmp(&(&b.release_date, &b.id)));
+
+        let mut report = SyncReport::default();
+        let mut unchanged = vec![];
+        for set in &sets {
+            let synced = manifest.sets.get(&set.id) == Some(&set.updated_at)
+                && current
+                    .as_ref()
+                    .is_some_and(|snapshot| snapshot.cards_path(&set.id).exists());
+            if synced {
+                unchanged.push(&set.id);
+            } else {
+                report.updated_sets.push(set.id.clone());
+            }
+        }
+        report.removed_sets = manifest
+            .sets
+            .keys()
+            .filter(|id| !sets.iter().any(|set| &set.id == *id))
+            .cloned()
+            .collect();
+
+        if report.is_unchanged() && current.is_some() {
+            report.revision = manifest.revision;
+            return Ok(report);
+        }
+
+        manifest.revision += 1;
+        let staging = Snapshot {
+            dir: self.staging_dir(manifest.revision),
+            revision: manifest.revision,
+        };
+        remove_dir_if_exists(&staging.dir)?;
+        fs::create_dir_all(staging.dir.join("cards").join(LANGUAGE))?;
+
+        if let Some(current) = &current {
+            for id in unchanged {
+                link_or_copy(&current.cards_path(id), &staging.cards_path(id))?;
+            }
+        }
+        for set in sets
+            .iter()
+            .filter(|set| report.updated_sets.contains(&set.id))
+        {
+            let cards = client.get_set_cards(&set.id).await?;
+            write_json(&staging.cards_path(&set.id), &cards)?;
+            manifest.sets.insert(set.id.clone(), set.updated_at.clone());
+        }
+        for id in &report.removed_sets {
+            manifest.sets.remove(id);
+        }
+        write_json(&staging.sets_path(), &sets)?;
+        write_json(&staging.manifest_path(), &manifest)?;
+
+        let snapshot = self.snapshot(manifest.revision);
+        remove_dir_if_exists(&snapshot.dir)?;
+        fs::rename(&staging.dir, &snapshot.dir)?;
+
+        let pointer = self.dir.join("current");
+        let tmp = pointer.with_extension("tmp");
+        fs::write(&tmp, manifest.revision.to_string())?;
+        fs::rename(tmp, pointer)?;
+
+        report.revision = manifest.revision;
+        Ok(report)
+    }
+
+    /// Reads every set of the current snapshot, ordered by release date.
+    pub fn sets(&self) -> Result<Vec<Set>, MirrorError> {
+        self.require_current()?.sets()
+    }
+
+    /// Reads the cards of a set in the current snapshot, ordered by collector number.
+    pub fn cards(&self, set_id: &str) -> Result<Vec<Card>, MirrorError> {
+        self.require_current()?.cards(set_id)
+    }
+
+    /// Reads every card of the current snapshot, set by set.
+    pub fn all_cards(&self) -> Result<Vec<Card>, MirrorError> {
+        self.require_current()?.all_cards()
+    }
+
+    /// Loads the current snapshot into memory, see [`Snapshot::store`].
+    pub fn store(&self) -> Result<MemoryStore, MirrorError> {
+        self.require_current()?.store()
+    }
+
+    fn require_current(&self) -> Result<Snapshot, MirrorError> {
+        self.current()?.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, "the mirror has never been synced").into()
+        })
+    }
+
+    fn snapshot_dir(&self, name: &str) -> PathBuf {
+        self.dir.join("snapshots").join(name)
+    }
+
+    /// Where a sync writes the snapshot of `revision` before it is complete
+    fn staging_dir(&self, revision: u64) -> PathBuf {
+        self.snapshot_dir(&format!("{}.tmp", revision))
+    }
+}
+
+impl Snapshot {
+    /// The revision of the snapshot.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// The directory of the snapshot, which can be loaded with
+    /// [`Dataset::load`](crate::dataset::Dataset::load).
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Reads the manifest of the snapshot.
+    ///
+    /// # Errors
+    /// Fails if the manifest can't be read or was written by an incompatible version of this crate.
+    pub fn manifest(&self) -> Result<Manifest, MirrorError> {
+        let manifest: Manifest = read_json(&self.manifest_path())?;
+        if manifest.format_version != FORMAT_VERSION {
+            return Err(MirrorError::UnsupportedFormat(manifest.format_version));
+        }
+
+        Ok(manifest)
+    }
+
+    /// Reads every set, ordered by release date.
+    pub fn sets(&self) -> Result<Vec<Set>, MirrorError> {
+        read_json(&self.sets_path())
+    }
+
+    /// Reads the cards of a set, ordered by collector number.
+    pub fn cards(&self, set_id: &str) -> Result<Vec<Card>, MirrorError> {
+        read_json(&self.cards_path(set_id))
+    }
+
+    /// Reads every card, set by set.
+    pub fn all_cards(&self) -> Result<Vec<Card>, MirrorError> {
+        let mut cards = vec![];
+        for set in self.sets()? {
+            cards.extend(self.cards(&set.id)?);
+        }
+
+        Ok(cards)
+    }
+
+    /// Loads the whole snapshot into memory, to be queried like the API through its
+    /// [`CardSource`](crate::source::CardSource) and [`SetSource`](crate::source::SetSource) implementations.
+    pub fn store(&self) -> Result<MemoryStore, MirrorError> {
+        Ok(MemoryStore::new(self.sets()?, self.all_cards()?))
+    }
+
+    fn manifest_path(&self) -> PathBuf {
+        self.dir.join("manifest.json")
+    }
+
+    fn sets_path(&self) -> PathBuf {
+        self.dir.join("sets").join(format!("{}.json", LANGUAGE))
+    }
+
+    fn cards_path(&self, set_id: &str) -> PathBuf {
+        self.dir
+            .join("cards")
+            .join(LANGUAGE)
+            .join(format!("{}.json", set_id))
+    }
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, MirrorError> {
+    Ok(serde_json::from_slice(&fs::read(path)?)?)
+}
+
+fn write_json<T: Serialize + ?Sized>(path: &Path, value: &T) -> Result<(), MirrorError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(path, serde_json::to_vec_pretty(value)?)?;
+    Ok(())
+}
+
+/// Shares an unchanged file with the previous snapshot, copying it where hard links aren't supported
+fn link_or_copy(from: &Path, to: &Path) -> io::Result<()> {
+    fs::hard_link(from, to).or_else(|_| fs::copy(from, to).map(|_| ()))
+}
+
+fn remove_dir_if_exists(dir: &Path) -> io::Result<()> {
+    match fs::remove_dir_all(dir) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
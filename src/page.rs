@@ -0,0 +1,117 @@
+use crate::{card::SearchCardsRequest, client::DataEnvelope, set::SearchSetsRequest};
+
+/// The page size the API uses when none is requested.
+pub(crate) const DEFAULT_PAGE_SIZE: u8 = 250;
+
+/// A single page of search results.
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    /// The items on this page.
+    pub data: Vec<T>,
+    /// The page number, starting at 1.
+    pub page: u16,
+    /// The maximum number of items per page.
+    pub page_size: u8,
+    /// The number of items on this page.
+    pub count: usize,
+    /// The total number of items matching the search, across all pages.
+    pub total_count: usize,
+}
+
+impl<T> Page<T> {
+    /// Builds a page from a response, falling back to the requested values for anything the API left out.
+    pub(crate) fn from_envelope(envelope: DataEnvelope<Vec<T>>, page: u16, page_size: u8) -> Self {
+        let page = envelope
+            .page
+            .and_then(|p| u16::try_from(p).ok())
+            .unwrap_or(page);
+        let page_size = envelope
+            .page_size
+            .and_then(|p| u8::try_from(p).ok())
+            .unwrap_or(page_size);
+        let count = envelope.count.unwrap_or(envelope.data.len());
+        let total_count = envelope
+            .total_count
+            .unwrap_or(usize::from(page.saturating_sub(1)) * usize::from(page_size) + count);
+
+        Page {
+            data: envelope.data,
+            page,
+            page_size,
+            count,
+            total_count,
+        }
+    }
+
+    /// The number of pages needed to hold every matching item.
+    pub fn total_pages(&self) -> usize {
+        if self.page_size == 0 {
+            return 0;
+        }
+
+        self.total_count.div_ceil(usize::from(self.page_size))
+    }
+
+    /// Whether there are more items after this page.
+    pub fn has_next(&self) -> bool {
+        usize::from(self.page) < self.total_pages()
+    }
+
+    /// Builds the request for the following page from the request that returned this page,
+    /// or `None` if this is the last page.
+    ///
+    /// ```no_run
+    /// # use pokemon_tcg_sdk::{card::SearchCardsRequest, client::Client};
+    /// # async fn run() -> Result<(), pokemon_tcg_sdk::errors::ClientError> {
+    /// let client = Client::default();
+    /// let request = SearchCardsRequest::new("name:celebi");
+    /// let page = client.search_cards(request.clone()).await?;
+    /// if let Some(next) = page.next_request(&request) {
+    ///     let next_page = client.search_cards(next).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn next_request<R: PageRequest>(&self, request: &R) -> Option<R> {
+        if !self.has_next() {
+            return None;
+        }
+
+        Some(request.at_page(self.page.checked_add(1)?, self.page_size))
+    }
+}
+
+impl<T> IntoIterator for Page<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+/// A search request that can be moved to another page.
+pub trait PageRequest: Sized {
+    /// Returns a copy of the request that asks for the given page and page size.
+    fn at_page(&self, page: u16, page_size: u8) -> Self;
+}
+
+impl PageRequest for SearchCardsRequest {
+    fn at_page(&self, page: u16, page_size: u8) -> Self {
+        SearchCardsRequest {
+            page: Some(page),
+            page_size: Some(page_size),
+            ..self.clone()
+        }
+    }
+}
+
+impl PageRequest for SearchSetsRequest {
+    fn at_page(&self, page: u16, page_size: u8) -> Self {
+        SearchSetsRequest {
+            page: Some(page),
+            page_size: Some(page_size),
+            ..self.clone()
+        }
+    }
+}
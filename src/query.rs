@@ -0,0 +1,260 @@
+//! Builders, a parser and an offline evaluator for the Lucene-like search syntax used by the `q` parameter.
+//!
+//! https://docs.pokemontcg.io/api-reference/cards/search-cards
+//!
+//! ```
+//! # use pokemon_tcg_sdk::query::{CardQuery, Match};
+//! let query = CardQuery::new()
+//!     .name(Match::wildcard("char*"))
+//!     .hp(Match::range(100..))
+//!     .not(CardQuery::new().subtype("Mega"));
+//!
+//! assert_eq!(query.to_string(), "name:char* hp:[100 TO *] -subtypes:Mega");
+//! ```
+
+mod card;
+mod eval;
+mod order;
+mod parser;
+mod set;
+
+use std::{
+    fmt::{self, Display, Write},
+    ops::{Bound, RangeBounds},
+};
+
+pub use self::{
+    card::{CardField, CardQuery},
+    order::{CardSort, Direction, OrderBy, SetSort, SortField},
+    parser::QueryError,
+    set::{SetField, SetQuery},
+};
+
+/// A parsed or built search query.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Query {
+    /// Matches items whose field, given as a dotted path such as `attacks.name`, matches the value.
+    Field { field: String, value: Match },
+    /// Matches items that don't match the inner query.
+    Not(Box<Query>),
+    /// Matches items that match every inner query.
+    And(Vec<Query>),
+    /// Matches items that match at least one inner query.
+    Or(Vec<Query>),
+}
+
+/// How the value of a field is matched.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Match {
+    /// Matches a word or phrase within the field, such as `name:charizard` or `attacks.name:"Fire Blast"`.
+    Term(String),
+    /// Matches the whole field exactly, such as `!name:charizard`.
+    Exact(String),
+    /// Matches a pattern where `*` stands for any characters and `?` for a single one, such as `name:char*`.
+    Wildcard(String),
+    /// Matches values within a range, such as `hp:[100 TO *]`.
+    Range {
+        lower: Bound<String>,
+        upper: Bound<String>,
+    },
+}
+
+impl Match {
+    /// Matches a word or phrase within the field.
+    pub fn term(value: impl ToString) -> Self {
+        Match::Term(value.to_string())
+    }
+
+    /// Matches the whole field exactly.
+    pub fn exact(value: impl ToString) -> Self {
+        Match::Exact(value.to_string())
+    }
+
+    /// Matches a pattern where `*` stands for any characters and `?` for a single one.
+    pub fn wildcard(pattern: impl ToString) -> Self {
+        Match::Wildcard(pattern.to_string())
+    }
+
+    /// Matches values within a range. Rust ranges keep their meaning, so `100..` renders as
+    /// `[100 TO *]` and `1..10` as `[1 TO 10}`. Use a tuple of [`Bound`]s for an exclusive lower bound.
+    pub fn range<T: ToString>(range: impl RangeBounds<T>) -> Self {
+        let bound = |bound: Bound<&T>| match bound {
+            Bound::Included(v) => Bound::Included(v.to_string()),
+            Bound::Excluded(v) => Bound::Excluded(v.to_string()),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+
+        Match::Range {
+            lower: bound(range.start_bound()),
+            upper: bound(range.end_bound()),
+        }
+    }
+}
+
+impl From<&str> for Match {
+    fn from(value: &str) -> Self {
+        Match::term(value)
+    }
+}
+
+impl From<String> for Match {
+    fn from(value: String) -> Self {
+        Match::Term(value)
+    }
+}
+
+impl From<u32> for Match {
+    fn from(value: u32) -> Self {
+        Match::term(value)
+    }
+}
+
+impl Query {
+    /// Matches items whose field matches the value.
+    pub fn field(field: &str, value: impl Into<Match>) -> Self {
+        Query::Field {
+            field: String::from(field),
+            value: value.into(),
+        }
+    }
+
+    fn fmt_grouped(&self, f: &mut fmt::Formatter<'_>, grouped: bool) -> fmt::Result {
+        if grouped {
+            f.write_char('(')?;
+            Display::fmt(self, f)?;
+            f.write_char(')')
+        } else {
+            Display::fmt(self, f)
+        }
+    }
+
+    /// Whether the query has to be wrapped in parentheses when nested
+    fn is_compound(&self) -> bool {
+        match self {
+            Query::And(queries) | Query::Or(queries) => queries.len() > 1,
+            _ => false,
+        }
+    }
+}
+
+impl Display for Query {
+    /// Renders the query in the syntax of the `q` parameter.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Query::Field { field, value } => {
+                if let Match::Exact(_) = value {
+                    f.write_char('!')?;
+                }
+                write!(f, "{}:", field)?;
+                match value {
+                    Match::Term(term) | Match::Exact(term) => write_term(f, term),
+                    Match::Wildcard(pattern) => write_wildcard(f, pattern),
+                    Match::Range { lower, upper } => {
+                        f.write_char(match lower {
+                            Bound::Excluded(_) => '{',
+                            _ => '[',
+                        })?;
+                        write_bound(f, lower)?;
+                        f.write_str(" TO ")?;
+                        write_bound(f, upper)?;
+                        f.write_char(match upper {
+                            Bound::Excluded(_) => '}',
+                            _ => ']',
+                        })
+                    }
+                }
+            }
+            Query::Not(query) => {
+                f.write_char('-')?;
+                query.fmt_grouped(f, query.is_compound())
+            }
+            Query::And(queries) => {
+                for (i, query) in queries.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(' ')?;
+                    }
+                    query.fmt_grouped(f, matches!(query, Query::Or(_)) && query.is_compound())?;
+                }
+                Ok(())
+            }
+            Query::Or(queries) => {
+                for (i, query) in queries.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(" OR ")?;
+                    }
+                    query.fmt_grouped(f, matches!(query, Query::And(_)) && query.is_compound())?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Characters with a meaning in the query syntax
+fn is_special(c: char) -> bool {
+    matches!(
+        c,
+        '+' | '-'
+            | '='
+            | '&'
+            | '|'
+            | '!'
+            | '('
+            | ')'
+            | '{'
+            | '}'
+            | '['
+            | ']'
+            | '^'
+            | '"'
+            | '~'
+            | '*'
+            | '?'
+            | ':'
+            | '\\'
+            | '/'
+            | '<'
+            | '>'
+    )
+}
+
+/// Whether a value can be written without quotes
+fn is_bare(value: &str) -> bool {
+    !value.is_empty()
+        && !matches!(value, "AND" | "OR" | "NOT" | "TO")
+        && value.chars().all(|c| !c.is_whitespace() && !is_special(c))
+}
+
+/// Writes a term, quoting it as a phrase when it contains whitespace or special characters
+fn write_term(f: &mut fmt::Formatter<'_>, term: &str) -> fmt::Result {
+    if is_bare(term) {
+        return f.write_str(term);
+    }
+
+    f.write_char('"')?;
+    for c in term.chars() {
+        if c == '"' || c == '\\' {
+            f.write_char('\\')?;
+        }
+        f.write_char(c)?;
+    }
+    f.write_char('"')
+}
+
+/// Writes a wildcard pattern, escaping everything but the wildcards themselves
+fn write_wildcard(f: &mut fmt::Formatter<'_>, pattern: &str) -> fmt::Result {
+    for c in pattern.chars() {
+        if (is_special(c) && c != '*' && c != '?') || c.is_whitespace() {
+            f.write_char('\\')?;
+        }
+        f.write_char(c)?;
+    }
+    Ok(())
+}
+
+fn write_bound(f: &mut fmt::Formatter<'_>, bound: &Bound<String>) -> fmt::Result {
+    match bound {
+        Bound::Included(v) | Bound::Excluded(v) => write_term(f, v),
+        Bound::Unbounded => f.write_char('*'),
+    }
+}
@@ -0,0 +1,267 @@
+use std::{
+    borrow::Cow,
+    fmt::{self, Display},
+};
+
+use crate::card::SearchCardsRequest;
+
+use super::{Match, Query, SetField};
+
+/// The searchable fields of a card.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardField {
+    Id,
+    Name,
+    Supertype,
+    Subtypes,
+    Level,
+    Hp,
+    Types,
+    EvolvesFrom,
+    EvolvesTo,
+    Rules,
+    AncientTraitName,
+    AbilitiesName,
+    AbilitiesText,
+    AbilitiesType,
+    AttacksName,
+    AttacksText,
+    AttacksDamage,
+    AttacksCost,
+    AttacksConvertedEnergyCost,
+    WeaknessesType,
+    WeaknessesValue,
+    ResistancesType,
+    ResistancesValue,
+    RetreatCost,
+    ConvertedRetreatCost,
+    /// A field of the set the card belongs to, such as `set.id`.
+    Set(SetField),
+    Number,
+    Artist,
+    Rarity,
+    FlavorText,
+    NationalPokedexNumbers,
+    LegalitiesStandard,
+    LegalitiesExpanded,
+    LegalitiesUnlimited,
+    RegulationMark,
+    /// Any other field, given as its dotted path in the API.
+    Other(String),
+}
+
+impl CardField {
+    /// The dotted path of the field in the API, such as `attacks.name`.
+    pub fn path(&self) -> Cow<'_, str> {
+        let path = match self {
+            CardField::Id => "id",
+            CardField::Name => "name",
+            CardField::Supertype => "supertype",
+            CardField::Subtypes => "subtypes",
+            CardField::Level => "level",
+            CardField::Hp => "hp",
+            CardField::Types => "types",
+            CardField::EvolvesFrom => "evolvesFrom",
+            CardField::EvolvesTo => "evolvesTo",
+            CardField::Rules => "rules",
+            CardField::AncientTraitName => "ancientTrait.name",
+            CardField::AbilitiesName => "abilities.name",
+            CardField::AbilitiesText => "abilities.text",
+            CardField::AbilitiesType => "abilities.type",
+            CardField::AttacksName => "attacks.name",
+            CardField::AttacksText => "attacks.text",
+            CardField::AttacksDamage => "attacks.damage",
+            CardField::AttacksCost => "attacks.cost",
+            CardField::AttacksConvertedEnergyCost => "attacks.convertedEnergyCost",
+            CardField::WeaknessesType => "weaknesses.type",
+            CardField::WeaknessesValue => "weaknesses.value",
+            CardField::ResistancesType => "resistances.type",
+            CardField::ResistancesValue => "resistances.value",
+            CardField::RetreatCost => "retreatCost",
+            CardField::ConvertedRetreatCost => "convertedRetreatCost",
+            CardField::Set(field) => return Cow::Owned(format!("set.{}", field.path())),
+            CardField::Number => "number",
+            CardField::Artist => "artist",
+            CardField::Rarity => "rarity",
+            CardField::FlavorText => "flavorText",
+            CardField::NationalPokedexNumbers => "nationalPokedexNumbers",
+            CardField::LegalitiesStandard => "legalities.standard",
+            CardField::LegalitiesExpanded => "legalities.expanded",
+            CardField::LegalitiesUnlimited => "legalities.unlimited",
+            CardField::RegulationMark => "regulationMark",
+            CardField::Other(path) => path,
+        };
+
+        Cow::Borrowed(path)
+    }
+}
+
+impl Display for CardField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.path())
+    }
+}
+
+/// Builds a card search query. Every condition added to the builder must match.
+///
+/// ```
+/// # use pokemon_tcg_sdk::query::{CardField, CardQuery, Match};
+/// let query = CardQuery::new()
+///     .field(CardField::AttacksName, "Fire Blast")
+///     .types("Fire")
+///     .or(CardQuery::new().types("Water"));
+///
+/// assert_eq!(query.to_string(), r#"(attacks.name:"Fire Blast" types:Fire) OR types:Water"#);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CardQuery {
+    clauses: Vec<Query>,
+}
+
+impl CardQuery {
+    pub fn new() -> Self {
+        CardQuery::default()
+    }
+
+    /// Adds a condition on any field.
+    pub fn field(mut self, field: CardField, value: impl Into<Match>) -> Self {
+        self.clauses.push(Query::field(&field.path(), value));
+        self
+    }
+
+    pub fn id(self, value: impl Into<Match>) -> Self {
+        self.field(CardField::Id, value)
+    }
+
+    pub fn name(self, value: impl Into<Match>) -> Self {
+        self.field(CardField::Name, value)
+    }
+
+    pub fn supertype(self, value: impl Into<Match>) -> Self {
+        self.field(CardField::Supertype, value)
+    }
+
+    pub fn subtype(self, value: impl Into<Match>) -> Self {
+        self.field(CardField::Subtypes, value)
+    }
+
+    pub fn hp(self, value: impl Into<Match>) -> Self {
+        self.field(CardField::Hp, value)
+    }
+
+    pub fn types(self, value: impl Into<Match>) -> Self {
+        self.field(CardField::Types, value)
+    }
+
+    pub fn evolves_from(self, value: impl Into<Match>) -> Self {
+        self.field(CardField::EvolvesFrom, value)
+    }
+
+    pub fn attack_name(self, value: impl Into<Match>) -> Self {
+        self.field(CardField::AttacksName, value)
+    }
+
+    pub fn set(self, field: SetField, value: impl Into<Match>) -> Self {
+        self.field(CardField::Set(field), value)
+    }
+
+    pub fn set_id(self, value: impl Into<Match>) -> Self {
+        self.set(SetField::Id, value)
+    }
+
+    pub fn number(self, value: impl Into<Match>) -> Self {
+        self.field(CardField::Number, value)
+    }
+
+    pub fn artist(self, value: impl Into<Match>) -> Self {
+        self.field(CardField::Artist, value)
+    }
+
+    pub fn rarity(self, value: impl Into<Match>) -> Self {
+        self.field(CardField::Rarity, value)
+    }
+
+    pub fn national_pokedex_number(self, value: impl Into<Match>) -> Self {
+        self.field(CardField::NationalPokedexNumbers, value)
+    }
+
+    pub fn regulation_mark(self, value: impl Into<Match>) -> Self {
+        self.field(CardField::RegulationMark, value)
+    }
+
+    /// Requires the other query to match as well.
+    pub fn and(mut self, other: CardQuery) -> Self {
+        self.clauses.extend(other.clauses);
+        self
+    }
+
+    /// Matches cards that match either this query or the other one. Empty queries are ignored.
+    pub fn or(self, other: CardQuery) -> Self {
+        // An empty query has no condition to offer as an alternative
+        if other.is_empty() {
+            return self;
+        }
+        if self.is_empty() {
+            return other;
+        }
+
+        let mut alternatives = vec![];
+        for query in [self.into_query(), other.into_query()] {
+            match query {
+                Query::Or(queries) => alternatives.extend(queries),
+                query => alternatives.push(query),
+            }
+        }
+
+        CardQuery {
+            clauses: vec![Query::Or(alternatives)],
+        }
+    }
+
+    /// Excludes cards that match the other query, unless it is empty.
+    pub fn not(mut self, other: CardQuery) -> Self {
+        if other.is_empty() {
+            return self;
+        }
+
+        self.clauses.push(Query::Not(Box::new(other.into_query())));
+        self
+    }
+
+    /// Whether no condition has been added yet.
+    pub fn is_empty(&self) -> bool {
+        self.clauses.is_empty()
+    }
+
+    pub fn into_query(mut self) -> Query {
+        if self.clauses.len() == 1 {
+            self.clauses.remove(0)
+        } else {
+            Query::And(self.clauses)
+        }
+    }
+}
+
+impl Display for CardQuery {
+    /// Renders the query in the syntax of the `q` parameter.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.clone().into_query(), f)
+    }
+}
+
+impl From<CardQuery> for Query {
+    fn from(query: CardQuery) -> Self {
+        query.into_query()
+    }
+}
+
+impl From<CardQuery> for SearchCardsRequest {
+    /// Builds a search for the query. An empty query searches all cards, without a `q` parameter.
+    fn from(query: CardQuery) -> Self {
+        if query.is_empty() {
+            return SearchCardsRequest::default();
+        }
+
+        SearchCardsRequest::new(&query.to_string())
+    }
+}
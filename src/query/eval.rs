@@ -0,0 +1,402 @@
+use std::{cmp::Ordering, ops::Bound};
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::{
+    card::{id::CollectorNumber, Card, SearchCardsRequest},
+    page::{Page, DEFAULT_PAGE_SIZE},
+    set::{SearchSetsRequest, Set},
+};
+
+use super::{Direction, Match, OrderBy, Query, QueryError, SortField};
+
+impl Query {
+    /// Whether an item matches the query, following the matching rules of the API:
+    /// terms and phrases match words case-insensitively, exact matches compare the whole value,
+    /// and a condition on a list (such as `attacks.cost`) matches if any element does.
+    ///
+    /// Field paths use the names of the API (`set.releaseDate`, `attacks.convertedEnergyCost`).
+    pub fn matches<T: Serialize>(&self, item: &T) -> bool {
+        serde_json::to_value(item).is_ok_and(|value| self.matches_value(&value))
+    }
+
+    /// Returns the items matching the query.
+    pub fn filter<T: Serialize + Clone>(&self, items: &[T]) -> Vec<T> {
+        items
+            .iter()
+            .filter(|item| self.matches(item))
+            .cloned()
+            .collect()
+    }
+
+    fn matches_value(&self, item: &Value) -> bool {
+        match self {
+            Query::Field { field, value } => {
+                let path: Vec<&str> = field.split('.').collect();
+                let mut leaves = vec![];
+                collect_leaves(item, &path, &mut leaves);
+                leaves
+                    .iter()
+                    .any(|leaf| matches_leaf(value, &leaf_text(leaf)))
+            }
+            Query::Not(query) => !query.matches_value(item),
+            Query::And(queries) => queries.iter().all(|query| query.matches_value(item)),
+            Query::Or(queries) => queries.iter().any(|query| query.matches_value(item)),
+        }
+    }
+}
+
+impl<F: SortField> OrderBy<F> {
+    /// Sorts items the way the API orders results. Items missing a sort field come last.
+    ///
+    /// Each field is compared one consistent way: `number` by [`CollectorNumber`]'s natural
+    /// order, numeric fields such as `hp` numerically, and any other field as normalized text.
+    pub fn sort<T: Serialize>(&self, items: &mut Vec<T>) {
+        let leaves: Vec<(Vec<Option<Value>>, T)> = items
+            .drain(..)
+            .map(|item| {
+                let value = serde_json::to_value(&item).unwrap_or(Value::Null);
+                let leaves = self
+                    .keys()
+                    .iter()
+                    .map(|(field, _)| {
+                        let path = field.path();
+                        let path: Vec<&str> = path.split('.').collect();
+                        let mut leaves = vec![];
+                        collect_leaves(&value, &path, &mut leaves);
+                        leaves.first().map(|leaf| (*leaf).clone())
+                    })
+                    .collect();
+                (leaves, item)
+            })
+            .collect();
+
+        let kinds: Vec<KeyKind> = self
+            .keys()
+            .iter()
+            .enumerate()
+            .map(|(i, (field, _))| {
+                KeyKind::of(
+                    &field.path(),
+                    leaves.iter().filter_map(|(leaves, _)| leaves[i].as_ref()),
+                )
+            })
+            .collect();
+
+        let mut keyed: Vec<(Vec<Option<SortKey>>, T)> = leaves
+            .into_iter()
+            .map(|(leaves, item)| {
+                let keys = leaves
+                    .iter()
+                    .zip(&kinds)
+                    .map(|(leaf, kind)| leaf.as_ref().and_then(|leaf| kind.key(leaf)))
+                    .collect();
+                (keys, item)
+            })
+            .collect();
+
+        keyed.sort_by(|(a, _), (b, _)| {
+            for ((a, b), (_, direction)) in a.iter().zip(b).zip(self.keys()) {
+                let ordering = match (a, b) {
+                    (Some(a), Some(b)) => match direction {
+                        Direction::Ascending => a.cmp(b),
+                        Direction::Descending => b.cmp(a),
+                    },
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            Ordering::Equal
+        });
+
+        items.extend(keyed.into_iter().map(|(_, item)| item));
+    }
+}
+
+/// How the values of a sort field are compared
+enum KeyKind {
+    CollectorNumber,
+    Numeric,
+    Text,
+}
+
+impl KeyKind {
+    /// Picks the comparison for a field: `number` is a collector number, `hp` and fields whose
+    /// values are all JSON numbers are numeric, and everything else is text
+    fn of<'a>(path: &str, mut leaves: impl Iterator<Item = &'a Value>) -> Self {
+        match path {
+            "number" => KeyKind::CollectorNumber,
+            "hp" => KeyKind::Numeric,
+            _ if leaves.all(Value::is_number) => KeyKind::Numeric,
+            _ => KeyKind::Text,
+        }
+    }
+
+    /// The key of a value, `None` when a numeric field holds something else
+    fn key(&self, leaf: &Value) -> Option<SortKey> {
+        let text = leaf_text(leaf);
+        match self {
+            KeyKind::CollectorNumber => Some(SortKey::CollectorNumber(CollectorNumber::from(
+                text.as_str(),
+            ))),
+            KeyKind::Numeric => text.trim().parse().ok().map(SortKey::Numeric),
+            KeyKind::Text => Some(SortKey::Text(normalize(&text))),
+        }
+    }
+}
+
+/// A sort key of a value. All the keys of a field are of the same kind.
+enum SortKey {
+    CollectorNumber(CollectorNumber),
+    Numeric(f64),
+    Text(String),
+}
+
+impl SortKey {
+    fn cmp(&self, other: &SortKey) -> Ordering {
+        match (self, other) {
+            (SortKey::CollectorNumber(a), SortKey::CollectorNumber(b)) => a.cmp(b),
+            (SortKey::Numeric(a), SortKey::Numeric(b)) => a.total_cmp(b),
+            (SortKey::Text(a), SortKey::Text(b)) => a.cmp(b),
+            (a, b) => a.rank().cmp(&b.rank()),
+        }
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            SortKey::CollectorNumber(_) => 0,
+            SortKey::Numeric(_) => 1,
+            SortKey::Text(_) => 2,
+        }
+    }
+}
+
+impl SearchCardsRequest {
+    /// Checks that the query follows the search syntax without sending the request.
+    pub fn validate(&self) -> Result<(), QueryError> {
+        self.query
+            .as_deref()
+            .map_or(Ok(()), |q| Query::parse(q).map(|_| ()))
+    }
+
+    /// Runs the search against a local collection of cards, including ordering and paging.
+    pub fn evaluate(&self, cards: &[Card]) -> Result<Page<Card>, QueryError> {
+        evaluate(
+            cards,
+            self.query.as_deref(),
+            self.order_by.as_ref(),
+            self.page,
+            self.page_size,
+        )
+    }
+}
+
+impl SearchSetsRequest {
+    /// Checks that the query follows the search syntax without sending the request.
+    pub fn validate(&self) -> Result<(), QueryError> {
+        self.query
+            .as_deref()
+            .map_or(Ok(()), |q| Query::parse(q).map(|_| ()))
+    }
+
+    /// Runs the search against a local collection of sets, including ordering and paging.
+    pub fn evaluate(&self, sets: &[Set]) -> Result<Page<Set>, QueryError> {
+        evaluate(
+            sets,
+            self.query.as_deref(),
+            self.order_by.as_ref(),
+            self.page,
+            self.page_size,
+        )
+    }
+}
+
+fn evaluate<T: Serialize + Clone, F: SortField>(
+    items: &[T],
+    query: Option<&str>,
+    order_by: Option<&OrderBy<F>>,
+    page: Option<u16>,
+    page_size: Option<u8>,
+) -> Result<Page<T>, QueryError> {
+    let query = Query::parse(query.unwrap_or_default())?;
+    let mut matching = query.filter(items);
+    if let Some(order_by) = order_by {
+        order_by.sort(&mut matching);
+    }
+
+    let page = page.unwrap_or(1).max(1);
+    let page_size = page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+    let total_count = matching.len();
+    let data: Vec<T> = matching
+        .into_iter()
+        .skip(usize::from(page - 1) * usize::from(page_size))
+        .take(usize::from(page_size))
+        .collect();
+
+    Ok(Page {
+        count: data.len(),
+        data,
+        page,
+        page_size,
+        total_count,
+    })
+}
+
+/// Collects the values found at a field path, flattening any lists along the way
+fn collect_leaves<'a>(value: &'a Value, path: &[&str], leaves: &mut Vec<&'a Value>) {
+    match value {
+        Value::Array(items) => {
+            for item in items {
+                collect_leaves(item, path, leaves);
+            }
+        }
+        Value::Object(map) => {
+            if let Some((segment, rest)) = path.split_first() {
+                if let Some(child) = lookup(map, segment) {
+                    collect_leaves(child, rest, leaves);
+                }
+            }
+        }
+        Value::Null => {}
+        leaf => {
+            if path.is_empty() {
+                leaves.push(leaf);
+            }
+        }
+    }
+}
+
+/// Finds a field by its API name in a serialized model, whose fields are snake_case
+fn lookup<'a>(map: &'a Map<String, Value>, name: &str) -> Option<&'a Value> {
+    if let Some(value) = map.get(name) {
+        return Some(value);
+    }
+
+    let mut snake_case = String::with_capacity(name.len() + 4);
+    for c in name.chars() {
+        if c.is_uppercase() {
+            snake_case.push('_');
+            snake_case.extend(c.to_lowercase());
+        } else {
+            snake_case.push(c);
+        }
+    }
+
+    // `type` is a keyword in Rust, so the models name those fields `type_name`
+    if snake_case == "type" {
+        snake_case.push_str("_name");
+    }
+
+    map.get(&snake_case)
+}
+
+fn leaf_text(leaf: &Value) -> String {
+    match leaf {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn matches_leaf(value: &Match, text: &str) -> bool {
+    match value {
+        Match::Term(term) => {
+            let needle = words(term);
+            let haystack = words(text);
+            !needle.is_empty()
+                && haystack
+                    .windows(needle.len())
+                    .any(|window| window == needle.as_slice())
+        }
+        Match::Exact(exact) => normalize(exact) == normalize(text),
+        Match::Wildcard(pattern) => {
+            let pattern = normalize(pattern);
+            wildcard_match(&pattern, &normalize(text))
+                || words(text)
+                    .iter()
+                    .any(|word| wildcard_match(&pattern, word))
+        }
+        Match::Range { lower, upper } => {
+            let above = match lower {
+                Bound::Included(v) => compare_text(text, v) != Ordering::Less,
+                Bound::Excluded(v) => compare_text(text, v) == Ordering::Greater,
+                Bound::Unbounded => true,
+            };
+            let below = match upper {
+                Bound::Included(v) => compare_text(text, v) != Ordering::Greater,
+                Bound::Excluded(v) => compare_text(text, v) == Ordering::Less,
+                Bound::Unbounded => true,
+            };
+            above && below
+        }
+    }
+}
+
+/// Compares numerically when both values are numbers, and case-insensitively otherwise
+fn compare_text(a: &str, b: &str) -> Ordering {
+    match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+        _ => normalize(a).cmp(&normalize(b)),
+    }
+}
+
+/// Lowercases text and strips the accents commonly found in card data, so that `pokemon` matches `Pokémon`
+fn normalize(text: &str) -> String {
+    text.chars()
+        .flat_map(char::to_lowercase)
+        .map(|c| match c {
+            'à' | 'á' | 'â' | 'ä' | 'ã' => 'a',
+            'è' | 'é' | 'ê' | 'ë' => 'e',
+            'ì' | 'í' | 'î' | 'ï' => 'i',
+            'ò' | 'ó' | 'ô' | 'ö' | 'õ' => 'o',
+            'ù' | 'ú' | 'û' | 'ü' => 'u',
+            'ñ' => 'n',
+            'ç' => 'c',
+            c => c,
+        })
+        .collect()
+}
+
+/// Splits text into normalized words, the way the API tokenizes text fields
+fn words(text: &str) -> Vec<String> {
+    normalize(text)
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|word| !word.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Matches text against a pattern where `*` stands for any characters and `?` for a single one
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
@@ -0,0 +1,157 @@
+use std::{
+    borrow::Cow,
+    fmt::{self, Display},
+};
+
+/// The direction of a sort key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Ascending,
+    Descending,
+}
+
+/// A field that search results can be ordered by.
+pub trait SortField {
+    /// The dotted path of the field in the API, such as `set.releaseDate`.
+    fn path(&self) -> Cow<'_, str>;
+}
+
+/// The fields cards can be ordered by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardSort {
+    Id,
+    Name,
+    Number,
+    Supertype,
+    Hp,
+    Rarity,
+    Artist,
+    ConvertedRetreatCost,
+    NationalPokedexNumbers,
+    SetId,
+    SetName,
+    SetSeries,
+    SetReleaseDate,
+    /// Any other field, given as its dotted path in the API.
+    Other(String),
+}
+
+impl SortField for CardSort {
+    fn path(&self) -> Cow<'_, str> {
+        Cow::Borrowed(match self {
+            CardSort::Id => "id",
+            CardSort::Name => "name",
+            CardSort::Number => "number",
+            CardSort::Supertype => "supertype",
+            CardSort::Hp => "hp",
+            CardSort::Rarity => "rarity",
+            CardSort::Artist => "artist",
+            CardSort::ConvertedRetreatCost => "convertedRetreatCost",
+            CardSort::NationalPokedexNumbers => "nationalPokedexNumbers",
+            CardSort::SetId => "set.id",
+            CardSort::SetName => "set.name",
+            CardSort::SetSeries => "set.series",
+            CardSort::SetReleaseDate => "set.releaseDate",
+            CardSort::Other(path) => path,
+        })
+    }
+}
+
+/// The fields sets can be ordered by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SetSort {
+    Id,
+    Name,
+    Series,
+    PrintedTotal,
+    Total,
+    PtcgoCode,
+    ReleaseDate,
+    UpdatedAt,
+    /// Any other field, given as its dotted path in the API.
+    Other(String),
+}
+
+impl SortField for SetSort {
+    fn path(&self) -> Cow<'_, str> {
+        Cow::Borrowed(match self {
+            SetSort::Id => "id",
+            SetSort::Name => "name",
+            SetSort::Series => "series",
+            SetSort::PrintedTotal => "printedTotal",
+            SetSort::Total => "total",
+            SetSort::PtcgoCode => "ptcgoCode",
+            SetSort::ReleaseDate => "releaseDate",
+            SetSort::UpdatedAt => "updatedAt",
+            SetSort::Other(path) => path,
+        })
+    }
+}
+
+/// An ordering of search results by one or more fields, such as `name,-number`.
+///
+/// ```
+/// # use pokemon_tcg_sdk::query::{CardSort, OrderBy};
+/// let order = OrderBy::asc(CardSort::SetReleaseDate).then_desc(CardSort::Number);
+///
+/// assert_eq!(order.to_string(), "set.releaseDate,-number");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderBy<F> {
+    keys: Vec<(F, Direction)>,
+}
+
+impl<F> OrderBy<F> {
+    /// Orders by the field, ascending.
+    pub fn asc(field: F) -> Self {
+        OrderBy {
+            keys: vec![(field, Direction::Ascending)],
+        }
+    }
+
+    /// Orders by the field, descending.
+    pub fn desc(field: F) -> Self {
+        OrderBy {
+            keys: vec![(field, Direction::Descending)],
+        }
+    }
+
+    /// Breaks ties by the field, ascending.
+    pub fn then_asc(mut self, field: F) -> Self {
+        self.keys.push((field, Direction::Ascending));
+        self
+    }
+
+    /// Breaks ties by the field, descending.
+    pub fn then_desc(mut self, field: F) -> Self {
+        self.keys.push((field, Direction::Descending));
+        self
+    }
+
+    /// The sort keys, most significant first.
+    pub fn keys(&self) -> &[(F, Direction)] {
+        &self.keys
+    }
+}
+
+impl<F> From<F> for OrderBy<F> {
+    fn from(field: F) -> Self {
+        OrderBy::asc(field)
+    }
+}
+
+impl<F: SortField> Display for OrderBy<F> {
+    /// Renders the ordering in the syntax of the `orderBy` parameter.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (field, direction)) in self.keys.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            if *direction == Direction::Descending {
+                f.write_str("-")?;
+            }
+            f.write_str(&field.path())?;
+        }
+        Ok(())
+    }
+}
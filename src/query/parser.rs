@@ -0,0 +1,351 @@
+use std::{iter::Peekable, ops::Bound, str::CharIndices, str::FromStr};
+
+use thiserror::Error;
+
+use super::{Match, Query};
+
+/// A query that doesn't follow the search syntax.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid query: {message} at position {position}")]
+pub struct QueryError {
+    /// What was wrong with the query.
+    pub message: String,
+    /// The byte offset in the query where the problem was found.
+    pub position: usize,
+}
+
+impl Query {
+    /// Parses a query written in the search syntax of the `q` parameter, such as
+    /// `name:char* (types:fire OR types:water) -subtypes:mega hp:[100 TO *]`.
+    ///
+    /// An empty query parses into an empty [`Query::And`], which matches everything.
+    pub fn parse(query: &str) -> Result<Query, QueryError> {
+        let mut parser = Parser {
+            input: query,
+            chars: query.char_indices().peekable(),
+        };
+
+        let parsed = parser.parse_or()?;
+        parser.skip_whitespace();
+        match parser.peek() {
+            None => Ok(parsed),
+            Some(')') => Err(parser.error("unexpected `)`")),
+            Some(c) => Err(parser.error(&format!("unexpected `{}`", c))),
+        }
+    }
+}
+
+impl FromStr for Query {
+    type Err = QueryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Query::parse(s)
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().map(|(_, c)| *c)
+    }
+
+    fn position(&mut self) -> usize {
+        self.chars
+            .peek()
+            .map_or(self.input.len(), |(position, _)| *position)
+    }
+
+    fn error(&mut self, message: &str) -> QueryError {
+        QueryError {
+            message: String::from(message),
+            position: self.position(),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.chars.next();
+        }
+    }
+
+    /// Whether `keyword` is the next word of the input
+    fn at_keyword(&mut self, keyword: &str) -> bool {
+        let rest = &self.input[self.position()..];
+        rest.starts_with(keyword)
+            && rest[keyword.len()..]
+                .chars()
+                .next()
+                .is_none_or(|c| c.is_whitespace() || c == '(')
+    }
+
+    /// Consumes `keyword` if it's the next word of the input
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        if !self.at_keyword(keyword) {
+            return false;
+        }
+
+        for _ in keyword.chars() {
+            self.chars.next();
+        }
+        true
+    }
+
+    fn parse_or(&mut self) -> Result<Query, QueryError> {
+        let mut alternatives = vec![self.parse_and()?];
+        loop {
+            self.skip_whitespace();
+            if !self.eat_keyword("OR") && !self.eat_keyword("||") {
+                break;
+            }
+
+            let alternative = self.parse_and()?;
+            if alternative == Query::And(vec![]) {
+                return Err(self.error("expected a condition after `OR`"));
+            }
+            alternatives.push(alternative);
+        }
+
+        Ok(if alternatives.len() == 1 {
+            alternatives.remove(0)
+        } else {
+            Query::Or(alternatives)
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<Query, QueryError> {
+        let mut clauses = vec![];
+        loop {
+            self.skip_whitespace();
+            if matches!(self.peek(), None | Some(')')) {
+                break;
+            }
+            if self.at_keyword("OR") || self.at_keyword("||") {
+                if clauses.is_empty() {
+                    return Err(self.error("expected a condition before `OR`"));
+                }
+                break;
+            }
+            if self.at_keyword("AND") || self.at_keyword("&&") {
+                if clauses.is_empty() {
+                    return Err(self.error("expected a condition before `AND`"));
+                }
+                self.eat_keyword("AND");
+                self.eat_keyword("&&");
+                self.skip_whitespace();
+                if matches!(self.peek(), None | Some(')')) {
+                    return Err(self.error("expected a condition after `AND`"));
+                }
+                continue;
+            }
+
+            clauses.push(self.parse_unary()?);
+        }
+
+        Ok(if clauses.len() == 1 {
+            clauses.remove(0)
+        } else {
+            Query::And(clauses)
+        })
+    }
+
+    fn parse_unary(&mut self) -> Result<Query, QueryError> {
+        if self.peek() == Some('-') {
+            self.chars.next();
+            return Ok(Query::Not(Box::new(self.parse_unary()?)));
+        }
+        if self.eat_keyword("NOT") {
+            self.skip_whitespace();
+            return Ok(Query::Not(Box::new(self.parse_unary()?)));
+        }
+        if self.peek() == Some('+') {
+            self.chars.next();
+        }
+
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Query, QueryError> {
+        if self.peek() == Some('(') {
+            self.chars.next();
+            let query = self.parse_or()?;
+            self.skip_whitespace();
+            if self.peek() != Some(')') {
+                return Err(self.error("expected `)`"));
+            }
+            if query == Query::And(vec![]) {
+                return Err(self.error("expected a condition inside `()`"));
+            }
+            self.chars.next();
+            return Ok(query);
+        }
+
+        let exact = self.peek() == Some('!');
+        if exact {
+            self.chars.next();
+        }
+
+        let field = self.parse_field()?;
+        let value = match self.peek() {
+            Some('"') => {
+                let phrase = self.parse_phrase()?;
+                if exact {
+                    Match::Exact(phrase)
+                } else {
+                    Match::Term(phrase)
+                }
+            }
+            Some('[') | Some('{') if !exact => self.parse_range()?,
+            _ => {
+                let (word, wildcard) = self.parse_word()?;
+                match (exact, wildcard) {
+                    (true, _) => Match::Exact(word),
+                    (false, true) => Match::Wildcard(word),
+                    (false, false) => Match::Term(word),
+                }
+            }
+        };
+
+        Ok(Query::Field { field, value })
+    }
+
+    /// Parses a dotted field name followed by `:`
+    fn parse_field(&mut self) -> Result<String, QueryError> {
+        let mut field = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_alphanumeric() || c == '.' || c == '_' {
+                field.push(c);
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+
+        if field.is_empty() {
+            return Err(self.error("expected a field name"));
+        }
+        if self.peek() != Some(':') {
+            return Err(self.error(&format!("expected `:` after the field `{}`", field)));
+        }
+        self.chars.next();
+
+        Ok(field)
+    }
+
+    /// Parses a quoted phrase, without the quotes
+    fn parse_phrase(&mut self) -> Result<String, QueryError> {
+        let start = self.position();
+        self.chars.next();
+
+        let mut phrase = String::new();
+        while let Some((_, c)) = self.chars.next() {
+            match c {
+                '"' => return Ok(phrase),
+                '\\' => match self.chars.next() {
+                    Some((_, escaped)) => phrase.push(escaped),
+                    None => break,
+                },
+                c => phrase.push(c),
+            }
+        }
+
+        Err(QueryError {
+            message: String::from("unterminated phrase"),
+            position: start,
+        })
+    }
+
+    /// Parses an unquoted value, returning whether it contains wildcards
+    fn parse_word(&mut self) -> Result<(String, bool), QueryError> {
+        let mut word = String::new();
+        let mut wildcard = false;
+        while let Some(c) = self.peek() {
+            match c {
+                c if c.is_whitespace() => break,
+                '(' | ')' | '[' | ']' | '{' | '}' | '"' => break,
+                '\\' => {
+                    self.chars.next();
+                    match self.chars.next() {
+                        Some((_, escaped)) => word.push(escaped),
+                        None => return Err(self.error("expected a character after `\\`")),
+                    }
+                    continue;
+                }
+                '*' | '?' => {
+                    wildcard = true;
+                    word.push(c);
+                }
+                c => word.push(c),
+            }
+            self.chars.next();
+        }
+
+        if word.is_empty() {
+            return Err(self.error("expected a value"));
+        }
+
+        Ok((word, wildcard))
+    }
+
+    /// Parses a range such as `[1 TO 10]` or `{* TO 2020/01/01}`
+    fn parse_range(&mut self) -> Result<Match, QueryError> {
+        let inclusive_lower = self.chars.next().map(|(_, c)| c) == Some('[');
+
+        self.skip_whitespace();
+        let lower = self.parse_bound(inclusive_lower)?;
+        self.skip_whitespace();
+        if !self.eat_keyword("TO") {
+            return Err(self.error("expected `TO` in range"));
+        }
+        self.skip_whitespace();
+
+        let upper_value = self.parse_bound_value()?;
+        self.skip_whitespace();
+        let inclusive_upper = match self.peek() {
+            Some(']') => true,
+            Some('}') => false,
+            _ => return Err(self.error("expected `]` or `}` to close the range")),
+        };
+        self.chars.next();
+
+        let upper = match upper_value {
+            None => Bound::Unbounded,
+            Some(v) if inclusive_upper => Bound::Included(v),
+            Some(v) => Bound::Excluded(v),
+        };
+        Ok(Match::Range { lower, upper })
+    }
+
+    fn parse_bound(&mut self, inclusive: bool) -> Result<Bound<String>, QueryError> {
+        Ok(match self.parse_bound_value()? {
+            None => Bound::Unbounded,
+            Some(v) if inclusive => Bound::Included(v),
+            Some(v) => Bound::Excluded(v),
+        })
+    }
+
+    /// Parses one side of a range, `None` standing for `*`
+    fn parse_bound_value(&mut self) -> Result<Option<String>, QueryError> {
+        if self.peek() == Some('"') {
+            return self.parse_phrase().map(Some);
+        }
+
+        let mut value = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() || c == ']' || c == '}' {
+                break;
+            }
+            value.push(c);
+            self.chars.next();
+        }
+
+        match value.as_str() {
+            "" => Err(self.error("expected a range bound")),
+            "*" => Ok(None),
+            _ => Ok(Some(value)),
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use std::{
+    borrow::Cow,
+    fmt::{self, Display},
+};
+
+use crate::set::SearchSetsRequest;
+
+use super::{Match, Query};
+
+/// The searchable fields of a set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SetField {
+    Id,
+    Name,
+    Series,
+    PrintedTotal,
+    Total,
+    LegalitiesStandard,
+    LegalitiesExpanded,
+    LegalitiesUnlimited,
+    PtcgoCode,
+    ReleaseDate,
+    UpdatedAt,
+    /// Any other field, given as its dotted path in the API.
+    Other(String),
+}
+
+impl SetField {
+    /// The dotted path of the field in the API, such as `legalities.standard`.
+    pub fn path(&self) -> Cow<'_, str> {
+        let path = match self {
+            SetField::Id => "id",
+            SetField::Name => "name",
+            SetField::Series => "series",
+            SetField::PrintedTotal => "printedTotal",
+            SetField::Total => "total",
+            SetField::LegalitiesStandard => "legalities.standard",
+            SetField::LegalitiesExpanded => "legalities.expanded",
+            SetField::LegalitiesUnlimited => "legalities.unlimited",
+            SetField::PtcgoCode => "ptcgoCode",
+            SetField::ReleaseDate => "releaseDate",
+            SetField::UpdatedAt => "updatedAt",
+            SetField::Other(path) => path,
+        };
+
+        Cow::Borrowed(path)
+    }
+}
+
+impl Display for SetField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.path())
+    }
+}
+
+/// Builds a set search query. Every condition added to the builder must match.
+///
+/// ```
+/// # use pokemon_tcg_sdk::query::{Match, SetField, SetQuery};
+/// let query = SetQuery::new()
+///     .series("Sword & Shield")
+///     .release_date(Match::range("2021/01/01"..))
+///     .field(SetField::LegalitiesStandard, "legal");
+///
+/// assert_eq!(
+///     query.to_string(),
+///     r#"series:"Sword & Shield" releaseDate:["2021/01/01" TO *] legalities.standard:legal"#
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SetQuery {
+    clauses: Vec<Query>,
+}
+
+impl SetQuery {
+    pub fn new() -> Self {
+        SetQuery::default()
+    }
+
+    /// Adds a condition on any field.
+    pub fn field(mut self, field: SetField, value: impl Into<Match>) -> Self {
+        self.clauses.push(Query::field(&field.path(), value));
+        self
+    }
+
+    pub fn id(self, value: impl Into<Match>) -> Self {
+        self.field(SetField::Id, value)
+    }
+
+    pub fn name(self, value: impl Into<Match>) -> Self {
+        self.field(SetField::Name, value)
+    }
+
+    pub fn series(self, value: impl Into<Match>) -> Self {
+        self.field(SetField::Series, value)
+    }
+
+    pub fn ptcgo_code(self, value: impl Into<Match>) -> Self {
+        self.field(SetField::PtcgoCode, value)
+    }
+
+    pub fn release_date(self, value: impl Into<Match>) -> Self {
+        self.field(SetField::ReleaseDate, value)
+    }
+
+    pub fn updated_at(self, value: impl Into<Match>) -> Self {
+        self.field(SetField::UpdatedAt, value)
+    }
+
+    pub fn total(self, value: impl Into<Match>) -> Self {
+        self.field(SetField::Total, value)
+    }
+
+    /// Requires the other query to match as well.
+    pub fn and(mut self, other: SetQuery) -> Self {
+        self.clauses.extend(other.clauses);
+        self
+    }
+
+    /// Matches sets that match either this query or the other one. Empty queries are ignored.
+    pub fn or(self, other: SetQuery) -> Self {
+        // An empty query has no condition to offer as an alternative
+        if other.is_empty() {
+            return self;
+        }
+        if self.is_empty() {
+            return other;
+        }
+
+        let mut alternatives = vec![];
+        for query in [self.into_query(), other.into_query()] {
+            match query {
+                Query::Or(queries) => alternatives.extend(queries),
+                query => alternatives.push(query),
+            }
+        }
+
+        SetQuery {
+            clauses: vec![Query::Or(alternatives)],
+        }
+    }
+
+    /// Excludes sets that match the other query, unless it is empty.
+    pub fn not(mut self, other: SetQuery) -> Self {
+        if other.is_empty() {
+            return self;
+        }
+
+        self.clauses.push(Query::Not(Box::new(other.into_query())));
+        self
+    }
+
+    /// Whether no condition has been added yet.
+    pub fn is_empty(&self) -> bool {
+        self.clauses.is_empty()
+    }
+
+    pub fn into_query(mut self) -> Query {
+        if self.clauses.len() == 1 {
+            self.clauses.remove(0)
+        } else {
+            Query::And(self.clauses)
+        }
+    }
+}
+
+impl Display for SetQuery {
+    /// Renders the query in the syntax of the `q` parameter.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.clone().into_query(), f)
+    }
+}
+
+impl From<SetQuery> for Query {
+    fn from(query: SetQuery) -> Self {
+        query.into_query()
+    }
+}
+
+impl From<SetQuery> for SearchSetsRequest {
+    /// Builds a search for the query. An empty query searches all sets, without a `q` parameter.
+    fn from(query: SetQuery) -> Self {
+        if query.is_empty() {
+            return SearchSetsRequest::default();
+        }
+
+        SearchSetsRequest::new(&query.to_string())
+    }
+}
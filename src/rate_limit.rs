@@ -0,0 +1,139 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use crate::time::{self, Instant};
+
+/// A client side limit on the number of requests sent to the API.
+///
+/// Requests are throttled with a token bucket: up to `burst` requests can be sent at once,
+/// after which the bucket refills at the configured rate.
+///
+/// ```
+/// # use pokemon_tcg_sdk::rate_limit::RateLimit;
+/// // 30 requests per minute, sent at most 5 at a time
+/// let limit = RateLimit::per_minute(30).burst(5);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    requests: u32,
+    period: Duration,
+    burst: u32,
+}
+
+impl RateLimit {
+    /// Allows `requests` requests per second, with a burst of the same size.
+    ///
+    /// # Panics
+    /// This method panics if `requests` is zero.
+    pub fn per_second(requests: u32) -> Self {
+        RateLimit::new(requests, Duration::from_secs(1))
+    }
+
+    /// Allows `requests` requests per minute, with a burst of the same size.
+    ///
+    /// # Panics
+    /// This method panics if `requests` is zero.
+    pub fn per_minute(requests: u32) -> Self {
+        RateLimit::new(requests, Duration::from_secs(60))
+    }
+
+    fn new(requests: u32, period: Duration) -> Self {
+        assert!(requests > 0, "a rate limit must allow at least one request");
+        RateLimit {
+            requests,
+            period,
+            burst: requests,
+        }
+    }
+
+    /// Sets how many requests can be sent at once before the rate applies. A burst of zero is treated as one.
+    pub fn burst(mut self, burst: u32) -> Self {
+        self.burst = burst.max(1);
+        self
+    }
+
+    /// The time it takes for a single token to be added back to the bucket
+    fn interval(&self) -> Duration {
+        self.period / self.requests
+    }
+}
+
+/// A token bucket shared by a client and all of its clones.
+#[derive(Debug, Clone)]
+pub(crate) struct RateLimiter {
+    limit: RateLimit,
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// Available tokens, negative when requests are already queued for future tokens
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(limit: RateLimit) -> Self {
+        RateLimiter {
+            limit,
+            bucket: Arc::new(Mutex::new(Bucket {
+                tokens: f64::from(limit.burst),
+                last_refill: Instant::now(),
+            })),
+        }
+    }
+
+    /// Reserves a token and returns how long the caller has to wait before it may send its request.
+    pub(crate) fn reserve(&self) -> Duration {
+        let interval = self.limit.interval().as_secs_f64();
+        let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+
+        let now = Instant::now();
+        let refilled = now.duration_since(bucket.last_refill).as_secs_f64() / interval;
+        bucket.tokens = (bucket.tokens + refilled).min(f64::from(self.limit.burst));
+        bucket.last_refill = now;
+        bucket.tokens -= 1.0;
+
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens * interval)
+        }
+    }
+
+    /// Waits until a request may be sent. If the future is dropped while waiting, such as when a
+    /// request times out or a stream is cancelled, the token is given back.
+    pub(crate) async fn acquire(&self) {
+        let wait = self.reserve();
+        if !wait.is_zero() {
+            let mut reservation = Reservation {
+                limiter: self,
+                used: false,
+            };
+            time::sleep(wait).await;
+            reservation.used = true;
+        }
+    }
+
+    /// Gives back a reserved token that won't be used
+    fn refund(&self) {
+        let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+        bucket.tokens = (bucket.tokens + 1.0).min(f64::from(self.limit.burst));
+    }
+}
+
+/// A token reserved by [`RateLimiter::acquire`], refunded unless the wait completed
+struct Reservation<'a> {
+    limiter: &'a RateLimiter,
+    used: bool,
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        if !self.used {
+            self.limiter.refund();
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+
+use crate::{
+    errors::ClientError,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Controls how failed requests are retried.
+///
+/// By default a [`Client`](crate::client::Client) makes a single attempt per request;
+/// configure a policy through [`ClientBuilder::retry_policy`](crate::client::ClientBuilder::retry_policy).
+///
+/// ```
+/// # use std::time::Duration;
+/// # use pokemon_tcg_sdk::retry::RetryPolicy;
+/// let policy = RetryPolicy {
+///     max_attempts: 5,
+///     initial_backoff: Duration::from_secs(1),
+///     ..RetryPolicy::default()
+/// };
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// The maximum number of attempts per request, including the first one.
+    pub max_attempts: u32,
+    /// The delay before the first retry. Every following retry doubles the delay.
+    pub initial_backoff: Duration,
+    /// The upper bound of the delay between two attempts.
+    pub max_backoff: Duration,
+    /// Randomizes each delay between half and the full backoff so that concurrent clients spread out.
+    pub jitter: bool,
+    /// Waits for the duration given by a `Retry-After` header instead of the computed backoff.
+    pub respect_retry_after: bool,
+    /// The upper bound of a delay given by a `Retry-After` header, so that a server asking for
+    /// hours doesn't stall the caller.
+    pub max_retry_after: Duration,
+    /// Decides which errors are worth retrying. Defaults to [`ClientError::is_transient`].
+    pub retry_on: fn(&ClientError) -> bool,
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..RetryPolicy::default()
+        }
+    }
+
+    /// Whether another attempt should be made after `attempt` attempts failed with `error`.
+    pub(crate) fn should_retry(&self, error: &ClientError, attempt: u32) -> bool {
+        attempt < self.max_attempts && (self.retry_on)(error)
+    }
+
+    /// The delay to wait after the given (1-based) failed attempt.
+    pub(crate) fn backoff(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        if let (true, Some(delay)) = (self.respect_retry_after, retry_after) {
+            return delay.min(self.max_retry_after);
+        }
+
+        let exponent = attempt.saturating_sub(1).min(31);
+        let delay = self
+            .initial_backoff
+            .saturating_mul(1 << exponent)
+            .min(self.max_backoff);
+
+        if self.jitter {
+            let half = delay / 2;
+            half + half.mul_f64(fastrand::f64())
+        } else {
+            delay
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts with an exponential backoff starting at 500ms, capped at 30s.
+    /// `Retry-After` delays are capped at 60s.
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            jitter: true,
+            respect_retry_after: true,
+            max_retry_after: Duration::from_secs(60),
+            retry_on: ClientError::is_transient,
+        }
+    }
+}
+
+/// Reads the `Retry-After` header, given either in seconds or as an HTTP date.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    // Compared as durations since the epoch, since the clock differs from std in the browser
+    let date = httpdate::parse_http_date(value)
+        .ok()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    Some(date.saturating_sub(now))
+}
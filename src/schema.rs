@@ -0,0 +1,141 @@
+//! Detects fields returned by the API that the models don't know about.
+//!
+//! Every model keeps such fields in its `extra` map instead of dropping them.
+//! [`UnknownFields::unknown_fields`] lists them with their path, and a client built with
+//! [`ClientBuilder::strict`](crate::client::ClientBuilder::strict) fails with
+//! [`ClientError::UnknownFields`] when there are any.
+//!
+//! ```no_run
+//! # use pokemon_tcg_sdk::{card::GetCardRequest, client::Client, schema::UnknownFields};
+//! # async fn run() -> Result<(), pokemon_tcg_sdk::errors::ClientError> {
+//! let card = Client::default().get_card(GetCardRequest::new("base1-4")).await?;
+//! for path in card.unknown_fields() {
+//!     eprintln!("warning: the API returned an unknown field {}", path);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    card::{
+        ability::Ability,
+        ancient_trait::AncientTrait,
+        attack::Attack,
+        cardmarket::{self, CardMarket},
+        image::Image,
+        resistance::Resistance,
+        tcgplayer::{self, TcgPlayer, TcgPlayerPrices},
+        weakness::Weakness,
+        Card,
+    },
+    errors::ClientError,
+    legality::Legalities,
+    page::Page,
+    set::{images::SetImages, Set},
+};
+
+/// A model that keeps the fields it doesn't know about.
+pub trait UnknownFields {
+    /// Appends the paths of the unknown fields, relative to `path`.
+    fn collect_unknown_fields(&self, path: &str, fields: &mut Vec<String>);
+
+    /// The paths of the unknown fields, such as `attacks[0].effect`.
+    fn unknown_fields(&self) -> Vec<String> {
+        let mut fields = vec![];
+        self.collect_unknown_fields("", &mut fields);
+        fields
+    }
+}
+
+impl<T: UnknownFields> UnknownFields for Option<T> {
+    fn collect_unknown_fields(&self, path: &str, fields: &mut Vec<String>) {
+        if let Some(value) = self {
+            value.collect_unknown_fields(path, fields);
+        }
+    }
+}
+
+impl<T: UnknownFields> UnknownFields for Vec<T> {
+    fn collect_unknown_fields(&self, path: &str, fields: &mut Vec<String>) {
+        for (i, value) in self.iter().enumerate() {
+            value.collect_unknown_fields(&format!("{}[{}]", path, i), fields);
+        }
+    }
+}
+
+impl<T: UnknownFields> UnknownFields for Page<T> {
+    fn collect_unknown_fields(&self, path: &str, fields: &mut Vec<String>) {
+        self.data.collect_unknown_fields(path, fields);
+    }
+}
+
+impl UnknownFields for TcgPlayerPrices {
+    fn collect_unknown_fields(&self, path: &str, fields: &mut Vec<String>) {
+        for (variant, prices) in self.iter() {
+            prices.collect_unknown_fields(&join(path, variant.as_str()), fields);
+        }
+    }
+}
+
+/// Implements [`UnknownFields`] for models with an `extra` map, given their fields that are
+/// models too and the names of those fields in the API
+macro_rules! unknown_fields {
+    ($($model:ty { $($name:literal => $field:ident),* $(,)? })*) => {
+        $(
+            impl UnknownFields for $model {
+                fn collect_unknown_fields(&self, path: &str, fields: &mut Vec<String>) {
+                    fields.extend(self.extra.keys().map(|key| join(path, key)));
+                    $(self.$field.collect_unknown_fields(&join(path, $name), fields);)*
+                }
+            }
+        )*
+    };
+}
+
+unknown_fields! {
+    Card {
+        "ancientTrait" => ancient_trait,
+        "abilities" => abilities,
+        "attacks" => attacks,
+        "weaknesses" => weaknesses,
+        "resistances" => resistances,
+        "set" => set,
+        "legalities" => legalities,
+        "images" => images,
+        "tcgplayer" => tcgplayer,
+        "cardmarket" => cardmarket,
+    }
+    Ability {}
+    AncientTrait {}
+    Attack {}
+    Weakness {}
+    Resistance {}
+    Image {}
+    TcgPlayer { "prices" => prices }
+    tcgplayer::Prices {}
+    CardMarket { "prices" => prices }
+    cardmarket::Prices {}
+    Legalities {}
+    Set { "legalities" => legalities, "images" => images }
+    SetImages {}
+}
+
+/// Fails with the unknown fields of a model when the client is strict
+pub(crate) fn check<T: UnknownFields>(strict: bool, value: T) -> Result<T, ClientError> {
+    if strict {
+        let fields = value.unknown_fields();
+        if !fields.is_empty() {
+            return Err(ClientError::UnknownFields(fields));
+        }
+    }
+
+    Ok(value)
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        String::from(key)
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
@@ -1,19 +1,29 @@
-mod images;
-mod legality;
+pub mod images;
+#[deprecated(note = "use `pokemon_tcg_sdk::legality` instead")]
+pub mod legality;
 
-use serde::{Deserialize, Serialize};
+use futures::{Stream, StreamExt};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 use crate::{
-    client::{ApiResult, Client},
+    card::Card,
+    client::Client,
     errors::ClientError,
+    legality::Legalities,
+    page::Page,
+    query::{OrderBy, SetSort},
+    schema,
+    transport::{select_param, SearchParams},
 };
+#[cfg(feature = "chrono")]
+use crate::{date, errors::DateError};
 
-use self::{images::SetImages, legality::Legality};
+use self::images::SetImages;
 
 /// The Set Object
 /// https://docs.pokemontcg.io/api-reference/sets/set-object
 #[derive(Debug, Clone, Deserialize, Serialize)]
-#[cfg_attr(test, derive(Default))]
 pub struct Set {
     /// Unique identifier for the object.
     pub id: String,
@@ -27,30 +37,95 @@ pub struct Set {
     /// The total number of cards in the set, including secret rares, alternate art, etc.
     pub total: usize,
     /// The legalities of the set. If a given format is not legal, it will not appear in the hash.
-    pub legalities: Legality,
+    pub legalities: Legalities,
     /// The code the Pokémon Trading Card Game Online uses to identify a set.
     #[serde(alias = "ptcgoCode")]
     pub ptcgo_code: Option<String>,
     /// The date the set was released (in the USA). Format is YYYY/MM/DD.
     #[serde(alias = "releaseDate")]
+    #[cfg_attr(
+        feature = "chrono",
+        serde(deserialize_with = "crate::date::serde_format::date")
+    )]
     pub release_date: String,
     /// The date and time the set was updated. Format is YYYY/MM/DD HH:MM:SS.
     #[serde(alias = "updatedAt")]
+    #[cfg_attr(
+        feature = "chrono",
+        serde(deserialize_with = "crate::date::serde_format::date_time")
+    )]
     pub updated_at: String,
     /// Any images associated with the set, such as symbol and logo.
     pub images: SetImages,
+    /// Fields returned by the API that this version of the crate doesn't model.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
+impl Set {
+    /// Get every card of this set, ordered by collector number. See [`Client::get_set_cards`].
+    pub async fn cards(&self, client: &Client) -> Result<Vec<Card>, ClientError> {
+        client.get_set_cards(&self.id).await
+    }
+}
+
+#[cfg(test)]
+impl Default for Set {
+    /// An empty set with valid dates, so that it can be deserialized with the `chrono` feature
+    fn default() -> Self {
+        Set {
+            id: String::new(),
+            name: String::new(),
+            series: String::new(),
+            printed_total: 0,
+            total: 0,
+            legalities: Legalities::default(),
+            ptcgo_code: None,
+            release_date: String::from("1970/01/01"),
+            updated_at: String::from("1970/01/01 00:00:00"),
+            images: SetImages::default(),
+            extra: Map::new(),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl Set {
+    /// The date the set was released, parsed from [`Set::release_date`](#structfield.release_date).
+    ///
+    /// # Errors
+    /// Fails if the field isn't in the `YYYY/MM/DD` format.
+    pub fn release_date(&self) -> Result<chrono::NaiveDate, DateError> {
+        date::parse_date(&self.release_date)
+    }
+
+    /// The date and time the set was updated, parsed from [`Set::updated_at`](#structfield.updated_at).
+    ///
+    /// # Errors
+    /// Fails if the field isn't in the `YYYY/MM/DD HH:MM:SS` format.
+    pub fn updated_at(&self) -> Result<chrono::NaiveDateTime, DateError> {
+        date::parse_date_time(&self.updated_at)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct GetSetRequest {
     pub id: String,
+    /// The fields to return, such as `id`, `name` or `images`. All fields are returned if empty.
+    /// Partial responses can be fetched with [`Client::get_set_as`].
+    pub select: Option<Vec<String>>,
 }
 
 impl GetSetRequest {
     pub fn new(id: &str) -> Self {
-        GetSetRequest { id: id.into() }
+        GetSetRequest {
+            id: id.into(),
+            select: None,
+        }
     }
 }
 
+#[derive(Debug, Clone, Default)]
 pub struct SearchSetsRequest {
     ///The search query.
     pub query: Option<String>,
@@ -59,7 +134,10 @@ pub struct SearchSetsRequest {
     /// The maximum amount of sets to return. Max of 250.
     pub page_size: Option<u8>,
     /// The field(s) to order the results by.
-    pub order_by: Option<String>,
+    pub order_by: Option<OrderBy<SetSort>>,
+    /// The fields to return, such as `id`, `name` or `images`. All fields are returned if empty.
+    /// Partial responses can be fetched with [`Client::search_sets_as`].
+    pub select: Option<Vec<String>>,
 }
 
 impl SearchSetsRequest {
@@ -69,6 +147,19 @@ impl SearchSetsRequest {
             page: None,
             page_size: None,
             order_by: None,
+            select: None,
+        }
+    }
+}
+
+impl From<SearchSetsRequest> for SearchParams {
+    fn from(request: SearchSetsRequest) -> Self {
+        SearchParams {
+            select: select_param(&request.select),
+            query: request.query,
+            page: request.page,
+            page_size: request.page_size,
+            order_by: request.order_by.map(|o| o.to_string()),
         }
     }
 }
@@ -78,83 +169,73 @@ impl Client {
     ///
     /// https://docs.pokemontcg.io/api-reference/sets/get-set
     pub async fn get_set(&self, request: GetSetRequest) -> Result<Set, ClientError> {
+        schema::check(self.strict, self.get_set_as(request).await?)
+    }
+
+    /// Fetch a single set into any deserializable type, such as a struct holding only
+    /// the fields listed in `request.select` or a `serde_json::Value`.
+    /// A [strict](crate::client::ClientBuilder::strict) client doesn't check the result for unknown fields.
+    ///
+    /// https://docs.pokemontcg.io/api-reference/sets/get-set
+    pub async fn get_set_as<T: DeserializeOwned>(
+        &self,
+        request: GetSetRequest,
+    ) -> Result<T, ClientError> {
         let set = self
-            .http_client
-            .get(format!("{}/sets/{}", self.base_url, request.id))
-            .send()
-            .await?
-            .json::<ApiResult<Set>>()
+            .fetch(
+                &format!("/sets/{}", request.id),
+                &[("select", select_param(&request.select))],
+            )
             .await?;
 
-        Client::parse_response(set)
+        Ok(set.data)
     }
 
     /// Search for one or many sets given a search query.
     ///
     /// https://docs.pokemontcg.io/api-reference/sets/search-cards
-    pub async fn search_sets(&self, request: SearchSetsRequest) -> Result<Vec<Set>, ClientError> {
-        let sets = self
-            .http_client
-            .get(format!("{}/sets", self.base_url))
-            .query(&[
-                ("q", request.query),
-                ("page", request.page.map(|p| p.to_string())),
-                ("pageSize", request.page_size.map(|p| p.to_string())),
-                ("orderBy", request.order_by),
-            ])
-            .send()
-            .await?
-            .json::<ApiResult<Vec<Set>>>()
-            .await?;
+    pub async fn search_sets(&self, request: SearchSetsRequest) -> Result<Page<Set>, ClientError> {
+        schema::check(self.strict, self.search_sets_as(request).await?)
+    }
+
+    /// Search for sets, deserializing each result into any type, such as a struct holding only
+    /// the fields listed in `request.select` or a `serde_json::Value`.
+    /// A [strict](crate::client::ClientBuilder::strict) client doesn't check the result for unknown fields.
+    ///
+    /// https://docs.pokemontcg.io/api-reference/sets/search-sets
+    pub async fn search_sets_as<T: DeserializeOwned>(
+        &self,
+        request: SearchSetsRequest,
+    ) -> Result<Page<T>, ClientError> {
+        self.fetch_page("/sets", &request.into()).await
+    }
 
-        Client::parse_response(sets)
+    /// Stream every set matching a search query, lazily fetching one page at a time.
+    ///
+    /// Paging starts at `request.page` (or the first page) and uses `request.page_size` (or the API default of 250) sets per page.
+    /// Dropping the stream stops any further requests.
+    pub fn stream_sets(
+        &self,
+        request: SearchSetsRequest,
+    ) -> impl Stream<Item = Result<Set, ClientError>> + '_ {
+        self.paginate("/sets", request.into())
+            .map(move |set| set.and_then(|set| schema::check(self.strict, set)))
     }
 
     /// Get all sets (automatically pages through data)
     pub async fn get_all_sets(&self) -> Result<Vec<Set>, ClientError> {
-        let mut page = 1;
-        let page_size = 250;
-        let mut total_pages: usize = 0;
-        let mut sets: Vec<Set> = vec![];
-
-        loop {
-            let resp = self
-                .http_client
-                .get(format!("{}/sets", self.base_url))
-                .query(&[("page", page)])
-                .send()
-                .await?
-                .json::<ApiResult<Vec<Set>>>()
-                .await?;
-
-            let total_count = if let ApiResult::Ok(ev) = &resp {
-                ev.total_count
-            } else {
-                None
-            };
-
-            match Client::parse_response(resp) {
-                Ok(mut cv) => {
-                    sets.append(&mut cv);
-                    if sets.len() % page_size != 0 {
-                        break;
-                    }
-                    if let Some(tc) = total_count {
-                        total_pages = ((tc / page_size) as f64).ceil() as usize;
-                    }
-
-                    if page > total_pages {
-                        break;
-                    }
-
-                    page += 1;
-                }
-                Err(e) => {
-                    return Err(e);
-                }
-            };
-        }
+        self.get_all_sets_matching(SearchSetsRequest::default())
+            .await
+    }
 
-        Ok(sets)
+    /// Get all sets matching a search query, keeping its order (automatically pages through data).
+    ///
+    /// Once the first page reveals the total count, the remaining pages are fetched concurrently,
+    /// up to the limit set by [`ClientBuilder::page_concurrency`](crate::client::ClientBuilder::page_concurrency).
+    pub async fn get_all_sets_matching(
+        &self,
+        request: SearchSetsRequest,
+    ) -> Result<Vec<Set>, ClientError> {
+        schema::check(self.strict, self.fetch_all("/sets", request.into()).await?)
     }
 }
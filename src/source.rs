@@ -0,0 +1,253 @@
+//! Traits over the places cards and sets can come from, so that code can be written once and run
+//! against the API, a local dataset, or a test fixture.
+//!
+//! ```no_run
+//! # use pokemon_tcg_sdk::{card::SearchCardsRequest, client::Client, dataset::Dataset,
+//! #     errors::ClientError, source::{CardSource, MemoryStore}};
+//! async fn count_charizards(source: &impl CardSource) -> Result<usize, ClientError> {
+//!     let page = source.search_cards(SearchCardsRequest::new("name:charizard")).await?;
+//!     Ok(page.total_count)
+//! }
+//!
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! let online = count_charizards(&Client::default()).await?;
+//! let offline = count_charizards(&MemoryStore::from(Dataset::load("./pokemon-tcg-data")?)).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{collections::BTreeSet, sync::Arc};
+
+use async_trait::async_trait;
+use reqwest::StatusCode;
+
+use crate::{
+    card::{Card, GetCardRequest, SearchCardsRequest},
+    client::Client,
+    dataset::Dataset,
+    errors::{ApiError, ClientError, ErrorEnvelope},
+    page::Page,
+    query::QueryError,
+    set::{GetSetRequest, SearchSetsRequest, Set},
+    types::{EnergyType, Rarity, Subtype, Supertype},
+};
+
+/// A source of cards and of the values of their types, subtypes, supertypes and rarities.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait CardSource {
+    /// Get a single card by its id.
+    async fn get_card(&self, request: GetCardRequest) -> Result<Card, ClientError>;
+
+    /// Search for one or many cards given a search query.
+    async fn search_cards(&self, request: SearchCardsRequest) -> Result<Page<Card>, ClientError>;
+
+    /// Get all possible types.
+    async fn get_types(&self) -> Result<Vec<EnergyType>, ClientError>;
+
+    /// Get all possible subtypes.
+    async fn get_subtypes(&self) -> Result<Vec<Subtype>, ClientError>;
+
+    /// Get all possible supertypes.
+    async fn get_supertypes(&self) -> Result<Vec<Supertype>, ClientError>;
+
+    /// Get all possible rarities.
+    async fn get_rarities(&self) -> Result<Vec<Rarity>, ClientError>;
+}
+
+/// A source of sets.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait SetSource {
+    /// Get a single set by its id.
+    async fn get_set(&self, request: GetSetRequest) -> Result<Set, ClientError>;
+
+    /// Search for one or many sets given a search query.
+    async fn search_sets(&self, request: SearchSetsRequest) -> Result<Page<Set>, ClientError>;
+}
+
+/// Queries the API, through the response and disk caches of the client when configured.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl CardSource for Client {
+    async fn get_card(&self, request: GetCardRequest) -> Result<Card, ClientError> {
+        Client::get_card(self, request).await
+    }
+
+    async fn search_cards(&self, request: SearchCardsRequest) -> Result<Page<Card>, ClientError> {
+        Client::search_cards(self, request).await
+    }
+
+    async fn get_types(&self) -> Result<Vec<EnergyType>, ClientError> {
+        Client::get_types(self).await
+    }
+
+    async fn get_subtypes(&self) -> Result<Vec<Subtype>, ClientError> {
+        Client::get_subtypes(self).await
+    }
+
+    async fn get_supertypes(&self) -> Result<Vec<Supertype>, ClientError> {
+        Client::get_supertypes(self).await
+    }
+
+    async fn get_rarities(&self) -> Result<Vec<Rarity>, ClientError> {
+        Client::get_rarities(self).await
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl SetSource for Client {
+    async fn get_set(&self, request: GetSetRequest) -> Result<Set, ClientError> {
+        Client::get_set(self, request).await
+    }
+
+    async fn search_sets(&self, request: SearchSetsRequest) -> Result<Page<Set>, ClientError> {
+        Client::search_sets(self, request).await
+    }
+}
+
+/// Cards and sets held in memory, searched with the same syntax and ordering as the API.
+///
+/// Errors mirror those of the API: unknown ids are [`ClientError::NotFound`] and invalid queries
+/// are [`ClientError::BadRequest`]. The `select` parameter is ignored, whole models are returned.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStore {
+    sets: Arc<Vec<Set>>,
+    cards: Arc<Vec<Card>>,
+}
+
+impl MemoryStore {
+    pub fn new(sets: Vec<Set>, cards: Vec<Card>) -> Self {
+        MemoryStore {
+            sets: Arc::new(sets),
+            cards: Arc::new(cards),
+        }
+    }
+
+    pub fn sets(&self) -> &[Set] {
+        &self.sets
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    /// The distinct values of a card attribute, sorted like the API sorts them
+    fn distinct<'a, T, I>(&'a self, values: impl Fn(&'a Card) -> I) -> Vec<T>
+    where
+        T: for<'s> From<&'s str>,
+        I: Iterator<Item = &'a str>,
+    {
+        let values: BTreeSet<&str> = self.cards.iter().flat_map(values).collect();
+        values.into_iter().map(T::from).collect()
+    }
+}
+
+impl From<Dataset> for MemoryStore {
+    fn from(dataset: Dataset) -> Self {
+        MemoryStore::new(dataset.sets, dataset.cards)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl CardSource for MemoryStore {
+    async fn get_card(&self, request: GetCardRequest) -> Result<Card, ClientError> {
+        self.cards
+            .iter()
+            .find(|card| card.id == request.id)
+            .cloned()
+            .ok_or_else(not_found)
+    }
+
+    async fn search_cards(&self, request: SearchCardsRequest) -> Result<Page<Card>, ClientError> {
+        request.evaluate(&self.cards).map_err(bad_request)
+    }
+
+    async fn get_types(&self) -> Result<Vec<EnergyType>, ClientError> {
+        Ok(self.distinct(|card| card.types.iter().flatten().map(EnergyType::as_str)))
+    }
+
+    async fn get_subtypes(&self) -> Result<Vec<Subtype>, ClientError> {
+        Ok(self.distinct(|card| card.subtypes.iter().flatten().map(Subtype::as_str)))
+    }
+
+    async fn get_supertypes(&self) -> Result<Vec<Supertype>, ClientError> {
+        Ok(self.distinct(|card| std::iter::once(card.supertype.as_str())))
+    }
+
+    async fn get_rarities(&self) -> Result<Vec<Rarity>, ClientError> {
+        Ok(self.distinct(|card| card.rarity.iter().map(Rarity::as_str)))
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl SetSource for MemoryStore {
+    async fn get_set(&self, request: GetSetRequest) -> Result<Set, ClientError> {
+        self.sets
+            .iter()
+            .find(|set| set.id == request.id)
+            .cloned()
+            .ok_or_else(not_found)
+    }
+
+    async fn search_sets(&self, request: SearchSetsRequest) -> Result<Page<Set>, ClientError> {
+        request.evaluate(&self.sets).map_err(bad_request)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<S: CardSource + Send + Sync + ?Sized> CardSource for Arc<S> {
+    async fn get_card(&self, request: GetCardRequest) -> Result<Card, ClientError> {
+        (**self).get_card(request).await
+    }
+
+    async fn search_cards(&self, request: SearchCardsRequest) -> Result<Page<Card>, ClientError> {
+        (**self).search_cards(request).await
+    }
+
+    async fn get_types(&self) -> Result<Vec<EnergyType>, ClientError> {
+        (**self).get_types().await
+    }
+
+    async fn get_subtypes(&self) -> Result<Vec<Subtype>, ClientError> {
+        (**self).get_subtypes().await
+    }
+
+    async fn get_supertypes(&self) -> Result<Vec<Supertype>, ClientError> {
+        (**self).get_supertypes().await
+    }
+
+    async fn get_rarities(&self) -> Result<Vec<Rarity>, ClientError> {
+        (**self).get_rarities().await
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<S: SetSource + Send + Sync + ?Sized> SetSource for Arc<S> {
+    async fn get_set(&self, request: GetSetRequest) -> Result<Set, ClientError> {
+        (**self).get_set(request).await
+    }
+
+    async fn search_sets(&self, request: SearchSetsRequest) -> Result<Page<Set>, ClientError> {
+        (**self).search_sets(request).await
+    }
+}
+
+fn not_found() -> ClientError {
+    ErrorEnvelope::from_status(StatusCode::NOT_FOUND).into()
+}
+
+fn bad_request(e: QueryError) -> ClientError {
+    ErrorEnvelope {
+        error: ApiError {
+            message: e.to_string(),
+            code: 400,
+        },
+    }
+    .into()
+}
@@ -0,0 +1,188 @@
+//! The parts of making requests that don't depend on the HTTP client, shared by the async
+//! [`Client`](crate::client::Client) and the blocking client: building URLs, turning responses
+//! into bodies or errors, decoding, retrying and paging.
+
+use std::time::Duration;
+
+use bytes::Bytes;
+use reqwest::{header::HeaderMap, StatusCode, Url};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    cache::{DiskCache, StoredResponse},
+    client::DataEnvelope,
+    errors::{ClientError, ErrorEnvelope},
+    page::{Page, DEFAULT_PAGE_SIZE},
+    retry::RetryPolicy,
+};
+
+/// The query parameters shared by the search endpoints
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SearchParams {
+    pub(crate) query: Option<String>,
+    pub(crate) page: Option<u16>,
+    pub(crate) page_size: Option<u8>,
+    pub(crate) order_by: Option<String>,
+    pub(crate) select: Option<String>,
+}
+
+impl SearchParams {
+    pub(crate) fn to_query(&self) -> [(&'static str, Option<String>); 5] {
+        [
+            ("q", self.query.clone()),
+            ("page", self.page.map(|p| p.to_string())),
+            ("pageSize", self.page_size.map(|p| p.to_string())),
+            ("orderBy", self.order_by.clone()),
+            ("select", self.select.clone()),
+        ]
+    }
+
+    /// The parameters of the first page to request when paging through every result,
+    /// with the page and page size filled in
+    pub(crate) fn first_page(self) -> SearchParams {
+        SearchParams {
+            page: Some(self.page.unwrap_or(1)),
+            page_size: Some(self.page_size.unwrap_or(DEFAULT_PAGE_SIZE)),
+            ..self
+        }
+    }
+
+    /// The parameters of the given page, keeping everything else
+    pub(crate) fn with_page(&self, page: u16) -> SearchParams {
+        SearchParams {
+            page: Some(page),
+            ..self.clone()
+        }
+    }
+
+    /// Builds the page returned for these parameters
+    pub(crate) fn page_of<T>(&self, envelope: DataEnvelope<Vec<T>>) -> Page<T> {
+        Page::from_envelope(
+            envelope,
+            self.page.unwrap_or(1),
+            self.page_size.unwrap_or(DEFAULT_PAGE_SIZE),
+        )
+    }
+
+    /// The parameters of the page after `page`, which was returned for these parameters,
+    /// or `None` if it was the last one
+    pub(crate) fn next_page<T>(&self, page: &Page<T>) -> Option<SearchParams> {
+        if !page.has_next() || page.data.is_empty() {
+            return None;
+        }
+
+        page.page.checked_add(1).map(|next| self.with_page(next))
+    }
+}
+
+/// Joins the fields of a `select` parameter into the comma separated form the API expects
+pub(crate) fn select_param(select: &Option<Vec<String>>) -> Option<String> {
+    select.as_ref().map(|fields| fields.join(","))
+}
+
+/// Builds the URL of a path relative to the base url, leaving out the query parameters that
+/// aren't set. The URL is also the key of the response in the caches.
+pub(crate) fn url(
+    base_url: &str,
+    path: &str,
+    query: &[(&str, Option<String>)],
+) -> Result<Url, ClientError> {
+    let mut url = Url::parse(&format!("{}{}", base_url, path)).map_err(ClientError::InvalidUrl)?;
+    let mut params = query
+        .iter()
+        .filter_map(|(name, value)| value.as_ref().map(|value| (name, value)))
+        .peekable();
+    if params.peek().is_some() {
+        url.query_pairs_mut().extend_pairs(params);
+    }
+
+    Ok(url)
+}
+
+/// The body of a successful response
+pub(crate) enum Body {
+    /// A new response
+    Fresh(Bytes),
+    /// The response stored on disk, which the API confirmed hasn't changed
+    NotModified(Bytes),
+}
+
+/// Turns a response into the body of a successful response or the error it stands for.
+///
+/// A `304 Not Modified` answer to a conditional request returns the body stored on disk.
+pub(crate) fn read_response(
+    stored: Option<&StoredResponse>,
+    status: StatusCode,
+    body: Bytes,
+) -> Result<Body, ClientError> {
+    if status == StatusCode::NOT_MODIFIED {
+        if let Some(stored) = stored {
+            return Ok(Body::NotModified(stored.body()));
+        }
+    }
+
+    if status.is_client_error() || status.is_server_error() {
+        let envelope = serde_json::from_slice::<ErrorEnvelope>(&body)
+            .unwrap_or_else(|_| ErrorEnvelope::from_status(status));
+        return Err(envelope.into());
+    }
+
+    Ok(Body::Fresh(body))
+}
+
+impl Body {
+    /// Stores a new response on disk, or marks the stored one as fresh, and returns the body.
+    ///
+    /// This reads and writes files, so the async client runs it off the executor.
+    pub(crate) fn save(
+        self,
+        disk_cache: Option<&DiskCache>,
+        stored: Option<&StoredResponse>,
+        url: &str,
+        headers: &HeaderMap,
+    ) -> Bytes {
+        match self {
+            Body::Fresh(body) => {
+                if let Some(disk) = disk_cache {
+                    disk.store(url, headers, &body);
+                }
+                body
+            }
+            Body::NotModified(body) => {
+                if let Some((disk, stored)) = disk_cache.zip(stored) {
+                    disk.touch(stored);
+                }
+                body
+            }
+        }
+    }
+}
+
+/// Decodes a response body into a generic data envelope ({data: T}), or returns the error of an
+/// error envelope ({error: ...}).
+///
+/// The error envelope is checked first, so that a data envelope that fails to decode reports
+/// why, such as a malformed date, and the path of the field that failed, instead of matching
+/// neither envelope.
+pub(crate) fn decode<T: DeserializeOwned>(body: &[u8]) -> Result<DataEnvelope<T>, ClientError> {
+    if let Ok(envelope) = serde_json::from_slice::<ErrorEnvelope>(body) {
+        return Err(envelope.into());
+    }
+
+    let deserializer = &mut serde_json::Deserializer::from_slice(body);
+    serde_path_to_error::deserialize(deserializer).map_err(ClientError::DecodeFailed)
+}
+
+/// How long to wait before making another attempt after `attempt` attempts, or `None` if the
+/// result of the last one should be returned as is
+pub(crate) fn retry_delay<T>(
+    policy: &RetryPolicy,
+    result: &Result<T, ClientError>,
+    attempt: u32,
+    retry_after: Option<Duration>,
+) -> Option<Duration> {
+    match result {
+        Err(e) if policy.should_retry(e, attempt) => Some(policy.backoff(attempt, retry_after)),
+        _ => None,
+    }
+}
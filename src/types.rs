@@ -1,66 +1,235 @@
-use crate::{
-    client::{ApiResult, Client},
-    errors::ClientError,
-};
+//! The values of the type, subtype, supertype and rarity fields of cards.
+//!
+//! Each enum has an `Unknown` variant holding values added to the API after this crate was
+//! released, so that new values never break deserialization.
+//!
+//! ```
+//! # use pokemon_tcg_sdk::types::{EnergyType, Subtype};
+//! assert_eq!("Fire".parse::<EnergyType>().unwrap(), EnergyType::Fire);
+//! assert_eq!(Subtype::VMax.as_str(), "VMAX");
+//! assert_eq!(
+//!     "Stage 4".parse::<Subtype>().unwrap(),
+//!     Subtype::Unknown(String::from("Stage 4"))
+//! );
+//! ```
+
+use crate::{client::Client, errors::ClientError};
+
+/// Declares an enum of the string values of a field, with an `Unknown` fallback
+macro_rules! string_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident => $value:literal,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        #[non_exhaustive]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)*
+            /// A value this version of the crate doesn't know about.
+            Unknown(String),
+        }
+
+        impl $name {
+            /// The value as returned by the API.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $value,)*
+                    $name::Unknown(value) => value,
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = std::convert::Infallible;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Ok($name::from(s))
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                match value {
+                    $($value => $name::$variant,)*
+                    _ => $name::Unknown(String::from(value)),
+                }
+            }
+        }
+
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = <String as serde::Deserialize>::deserialize(deserializer)?;
+                Ok($name::from(value.as_str()))
+            }
+        }
+
+        impl From<$name> for $crate::query::Match {
+            fn from(value: $name) -> Self {
+                $crate::query::Match::term(value)
+            }
+        }
+
+        #[cfg(test)]
+        impl Default for $name {
+            fn default() -> Self {
+                $name::Unknown(String::new())
+            }
+        }
+    };
+}
+
+pub(crate) use string_enum;
+
+string_enum! {
+    /// The supertype of a card.
+    pub enum Supertype {
+        Energy => "Energy",
+        Pokemon => "Pokémon",
+        Trainer => "Trainer",
+    }
+}
+
+string_enum! {
+    /// An energy type, used for the types of Pokémon, attack and retreat costs, weaknesses and
+    /// resistances.
+    pub enum EnergyType {
+        Colorless => "Colorless",
+        Darkness => "Darkness",
+        Dragon => "Dragon",
+        Fairy => "Fairy",
+        Fighting => "Fighting",
+        Fire => "Fire",
+        Grass => "Grass",
+        Lightning => "Lightning",
+        Metal => "Metal",
+        Psychic => "Psychic",
+        Water => "Water",
+    }
+}
+
+string_enum! {
+    /// A subtype of a card, such as the stage of a Pokémon or the kind of a Trainer.
+    pub enum Subtype {
+        AceSpec => "ACE SPEC",
+        Ancient => "Ancient",
+        Baby => "Baby",
+        Basic => "Basic",
+        Break => "BREAK",
+        Ex => "EX",
+        LowercaseEx => "ex",
+        FusionStrike => "Fusion Strike",
+        Future => "Future",
+        GoldenrodGameCorner => "Goldenrod Game Corner",
+        Gx => "GX",
+        Item => "Item",
+        Legend => "LEGEND",
+        LevelUp => "Level-Up",
+        Mega => "MEGA",
+        PokemonTool => "Pokémon Tool",
+        PokemonToolF => "Pokémon Tool F",
+        PrismStar => "Prism Star",
+        Radiant => "Radiant",
+        RapidStrike => "Rapid Strike",
+        Restored => "Restored",
+        RocketsSecretMachine => "Rocket's Secret Machine",
+        SingleStrike => "Single Strike",
+        Special => "Special",
+        Stadium => "Stadium",
+        Stage1 => "Stage 1",
+        Stage2 => "Stage 2",
+        Supporter => "Supporter",
+        TagTeam => "TAG TEAM",
+        TechnicalMachine => "Technical Machine",
+        Tera => "Tera",
+        V => "V",
+        VMax => "VMAX",
+        VStar => "VSTAR",
+        VUnion => "V-UNION",
+    }
+}
+
+string_enum! {
+    /// The rarity of a card.
+    pub enum Rarity {
+        AceSpecRare => "ACE SPEC Rare",
+        AmazingRare => "Amazing Rare",
+        ClassicCollection => "Classic Collection",
+        Common => "Common",
+        DoubleRare => "Double Rare",
+        HyperRare => "Hyper Rare",
+        IllustrationRare => "Illustration Rare",
+        Legend => "LEGEND",
+        Promo => "Promo",
+        RadiantRare => "Radiant Rare",
+        Rare => "Rare",
+        RareAce => "Rare ACE",
+        RareBreak => "Rare BREAK",
+        RareHolo => "Rare Holo",
+        RareHoloEx => "Rare Holo EX",
+        RareHoloGx => "Rare Holo GX",
+        RareHoloLvX => "Rare Holo LV.X",
+        RareHoloStar => "Rare Holo Star",
+        RareHoloV => "Rare Holo V",
+        RareHoloVMax => "Rare Holo VMAX",
+        RareHoloVStar => "Rare Holo VSTAR",
+        RarePrime => "Rare Prime",
+        RarePrismStar => "Rare Prism Star",
+        RareRainbow => "Rare Rainbow",
+        RareSecret => "Rare Secret",
+        RareShining => "Rare Shining",
+        RareShiny => "Rare Shiny",
+        RareShinyGx => "Rare Shiny GX",
+        RareUltra => "Rare Ultra",
+        ShinyRare => "Shiny Rare",
+        ShinyUltraRare => "Shiny Ultra Rare",
+        SpecialIllustrationRare => "Special Illustration Rare",
+        TrainerGalleryRareHolo => "Trainer Gallery Rare Holo",
+        UltraRare => "Ultra Rare",
+        Uncommon => "Uncommon",
+    }
+}
 
 impl Client {
     /// Get all possible types
     ///
     /// https://docs.pokemontcg.io/api-reference/types/get-types
-    pub async fn get_types(&self) -> Result<Vec<String>, ClientError> {
-        let types = self
-            .http_client
-            .get(format!("{}/types", self.base_url))
-            .send()
-            .await?
-            .json::<ApiResult<Vec<String>>>()
-            .await?;
-
-        Client::parse_response(types)
+    pub async fn get_types(&self) -> Result<Vec<EnergyType>, ClientError> {
+        Ok(self.fetch("/types", &[]).await?.data)
     }
 
     /// Get all possible subtypes
     ///
     /// https://docs.pokemontcg.io/api-reference/subtypes/get-subtypes
-    pub async fn get_subtypes(&self) -> Result<Vec<String>, ClientError> {
-        let types = self
-            .http_client
-            .get(format!("{}/subtypes", self.base_url))
-            .send()
-            .await?
-            .json::<ApiResult<Vec<String>>>()
-            .await?;
-
-        Client::parse_response(types)
+    pub async fn get_subtypes(&self) -> Result<Vec<Subtype>, ClientError> {
+        Ok(self.fetch("/subtypes", &[]).await?.data)
     }
 
     /// Get all possible supertypes
     ///
     /// https://docs.pokemontcg.io/api-reference/supertypes/get-supertypes
-    pub async fn get_supertypes(&self) -> Result<Vec<String>, ClientError> {
-        let types = self
-            .http_client
-            .get(format!("{}/supertypes", self.base_url))
-            .send()
-            .await?
-            .json::<ApiResult<Vec<String>>>()
-            .await?;
-
-        Client::parse_response(types)
+    pub async fn get_supertypes(&self) -> Result<Vec<Supertype>, ClientError> {
+        Ok(self.fetch("/supertypes", &[]).await?.data)
     }
 
     /// Get all possible rarities
     ///
     /// https://docs.pokemontcg.io/api-reference/rarities/get-rarities
-    pub async fn get_rarities(&self) -> Result<Vec<String>, ClientError> {
-        let types = self
-            .http_client
-            .get(format!("{}/rarities", self.base_url))
-            .send()
-            .await?
-            .json::<ApiResult<Vec<String>>>()
-            .await?;
-
-        Client::parse_response(types)
+    pub async fn get_rarities(&self) -> Result<Vec<Rarity>, ClientError> {
+        Ok(self.fetch("/rarities", &[]).await?.data)
     }
 }